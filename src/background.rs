@@ -0,0 +1,220 @@
+use nalgebra_glm as glm;
+use std::path::Path;
+
+use crate::render::Renderer;
+
+// Generador pseudoaleatorio mínimo (xorshift) para que el cielo sea reproducible
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self { Self(seed.max(1)) }
+
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+// Plano galáctico: concentra estrellas y un brillo difuso tenue
+const GALACTIC_NORMAL: [f32; 3] = [0.3, 0.9, 0.2];
+
+fn galactic_normal() -> glm::Vec3 {
+    glm::normalize(&glm::vec3(GALACTIC_NORMAL[0], GALACTIC_NORMAL[1], GALACTIC_NORMAL[2]))
+}
+
+pub struct Star {
+    pub dir: glm::Vec3,           // Dirección en espacio mundo (unitaria)
+    pub brightness: f32,          // [0,1] derivado de la magnitud
+    pub color: (f32, f32, f32),
+}
+
+pub struct Starfield {
+    pub stars: Vec<Star>,
+}
+
+// Tinte aproximado por temperatura: rojizas → blancas → azuladas
+fn star_tint(t: f32) -> (f32, f32, f32) {
+    let warm = (1.0, 0.72, 0.48);
+    let white = (1.0, 0.97, 0.92);
+    let cool = (0.68, 0.78, 1.0);
+    let lerp = |a: (f32,f32,f32), b: (f32,f32,f32), k: f32| {
+        (a.0 + (b.0 - a.0) * k, a.1 + (b.1 - a.1) * k, a.2 + (b.2 - a.2) * k)
+    };
+    if t < 0.5 { lerp(warm, white, t * 2.0) } else { lerp(white, cool, (t - 0.5) * 2.0) }
+}
+
+impl Starfield {
+    // `count` estrellas; ~40% concentradas cerca de un plano galáctico
+    pub fn generate(count: usize, seed: u64) -> Self {
+        let mut rng = XorShift::new(seed);
+        let galactic_normal = galactic_normal();
+        let mut stars = Vec::with_capacity(count);
+
+        for i in 0..count {
+            let z = rng.next_f32() * 2.0 - 1.0;
+            let phi = rng.next_f32() * std::f32::consts::TAU;
+            let r = (1.0 - z * z).max(0.0).sqrt();
+            let mut dir = glm::vec3(r * phi.cos(), z, r * phi.sin());
+
+            if i % 5 < 2 {
+                // Aplastar hacia el plano de la galaxia
+                let h = dir.dot(&galactic_normal);
+                dir = glm::normalize(&(dir - galactic_normal * h * 0.85));
+            }
+
+            // Magnitud aparente: muchas débiles, pocas brillantes (ley de potencias)
+            let m = rng.next_f32();
+            let brightness = 0.25 + 0.75 * m.powf(6.0);
+            let color = star_tint(rng.next_f32());
+
+            stars.push(Star { dir, brightness, color });
+        }
+
+        Self { stars }
+    }
+}
+
+// Imagen de cielo: equirectangular o cubemap de 6 caras (+X,-X,+Y,-Y,+Z,-Z)
+pub enum SkyImage {
+    Equirect(image::RgbImage),
+    Cubemap(Box<[image::RgbImage; 6]>),
+}
+
+impl SkyImage {
+    pub fn load_equirect(path: &str) -> Result<Self, String> {
+        let img = image::open(path).map_err(|e| format!("Error cargando '{}': {}", path, e))?;
+        Ok(SkyImage::Equirect(img.to_rgb8()))
+    }
+
+    // Busca px/nx/py/ny/pz/nz.png dentro de `dir`
+    pub fn load_cubemap(dir: &str) -> Result<Self, String> {
+        let names = ["px", "nx", "py", "ny", "pz", "nz"];
+        let mut faces = Vec::with_capacity(6);
+        for name in names {
+            let path = Path::new(dir).join(format!("{}.png", name));
+            let img = image::open(&path)
+                .map_err(|e| format!("Error cargando '{}': {}", path.display(), e))?;
+            faces.push(img.to_rgb8());
+        }
+        let faces: [image::RgbImage; 6] = faces.try_into().map_err(|_| "Cubemap incompleto".to_string())?;
+        Ok(SkyImage::Cubemap(Box::new(faces)))
+    }
+
+    // Color [0,1] en una dirección del mundo (muestreo nearest)
+    pub fn sample(&self, d: glm::Vec3) -> (f32, f32, f32) {
+        let (img, u, v) = match self {
+            SkyImage::Equirect(img) => {
+                let u = d.x.atan2(-d.z) / std::f32::consts::TAU + 0.5;
+                let v = d.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI;
+                (img, u, v)
+            }
+            SkyImage::Cubemap(faces) => {
+                let a = d.abs();
+                let (face, sc, tc, ma) = if a.x >= a.y && a.x >= a.z {
+                    if d.x > 0.0 { (0, -d.z, -d.y, a.x) } else { (1, d.z, -d.y, a.x) }
+                } else if a.y >= a.z {
+                    if d.y > 0.0 { (2, d.x, d.z, a.y) } else { (3, d.x, -d.z, a.y) }
+                } else if d.z > 0.0 {
+                    (4, d.x, -d.y, a.z)
+                } else {
+                    (5, -d.x, -d.y, a.z)
+                };
+                (&faces[face], 0.5 * (sc / ma + 1.0), 0.5 * (tc / ma + 1.0))
+            }
+        };
+        let (w, h) = img.dimensions();
+        let x = ((u * w as f32) as u32).min(w - 1);
+        let y = ((v * h as f32) as u32).min(h - 1);
+        let p = img.get_pixel(x, y);
+        (p[0] as f32 / 255.0, p[1] as f32 / 255.0, p[2] as f32 / 255.0)
+    }
+}
+
+// Fondo: color base + imagen de cielo opcional + estrellas.
+// Se dibuja con una proyección en perspectiva virtual (`fov`) y rota con la cámara.
+pub struct Background {
+    pub base_color: (u8, u8, u8),
+    pub stars: Starfield,
+    pub sky: Option<SkyImage>,
+    pub fov: f32,
+}
+
+impl Background {
+    pub fn new(base_color: (u8, u8, u8)) -> Self {
+        Self { base_color, stars: Starfield::generate(2500, 0x5eed_5747), sky: None, fov: 60f32.to_radians() }
+    }
+
+    // Radiancia [0,1] del cielo (sin estrellas) en una dirección del mundo
+    pub fn sample(&self, d: glm::Vec3) -> (f32, f32, f32) {
+        if let Some(sky) = &self.sky {
+            return sky.sample(d);
+        }
+        let (r, g, b) = self.base_color;
+        let h = d.dot(&galactic_normal());
+        let ripple = 0.75 + 0.25 * (d.x * 7.0 + d.z * 5.0).sin() * (d.y * 9.0).cos();
+        let glow = (-(h / 0.16).powi(2)).exp() * ripple;
+        (
+            r as f32 / 255.0 + glow * 0.05,
+            g as f32 / 255.0 + glow * 0.045,
+            b as f32 / 255.0 + glow * 0.07,
+        )
+    }
+
+    // `camera`: rotación mundo → vista (la misma que aplican los WASD)
+    pub fn draw(&self, renderer: &mut Renderer, camera: &glm::Mat4) {
+        let (w, h) = (renderer.width, renderer.height);
+        let hw = w as f32 * 0.5;
+        let hh = h as f32 * 0.5;
+        let focal = hw / (self.fov * 0.5).tan();
+        let rot = glm::mat4_to_mat3(camera);
+        let inv = rot.transpose();
+
+        // Cielo: una dirección por píxel, llevada al mundo con la rotación inversa
+        for y in 0..h {
+            for x in 0..w {
+                let d_view = glm::vec3((x as f32 + 0.5 - hw) / focal, (hh - y as f32 - 0.5) / focal, 1.0);
+                let (r, g, b) = self.sample(glm::normalize(&(inv * d_view)));
+                renderer.color[y * w + x] = pack(r * 255.0, g * 255.0, b * 255.0);
+            }
+        }
+
+        // Estrellas: proyectar cada dirección y sumar su brillo
+        for star in &self.stars.stars {
+            let d = rot * star.dir;
+            if d.z <= 1e-3 { continue; }
+            let sx = hw + focal * d.x / d.z;
+            let sy = hh - focal * d.y / d.z;
+            if sx < 0.0 || sy < 0.0 || sx >= w as f32 || sy >= h as f32 { continue; }
+
+            let cx = sx as usize;
+            let cy = sy as usize;
+            add_light(renderer, cx, cy, star.color, star.brightness);
+
+            // Halo en cruz para las más brillantes
+            if star.brightness > 0.45 {
+                let halo = star.brightness * 0.35;
+                if cx > 0     { add_light(renderer, cx - 1, cy, star.color, halo); }
+                if cx + 1 < w { add_light(renderer, cx + 1, cy, star.color, halo); }
+                if cy > 0     { add_light(renderer, cx, cy - 1, star.color, halo); }
+                if cy + 1 < h { add_light(renderer, cx, cy + 1, star.color, halo); }
+            }
+        }
+    }
+}
+
+#[inline]
+fn pack(r: f32, g: f32, b: f32) -> u32 {
+    let c = |v: f32| v.clamp(0.0, 255.0) as u32;
+    (0xFFu32 << 24) | (c(r) << 16) | (c(g) << 8) | c(b)
+}
+
+fn add_light(renderer: &mut Renderer, x: usize, y: usize, color: (f32, f32, f32), k: f32) {
+    let idx = y * renderer.width + x;
+    let px = renderer.color[idx];
+    let r = ((px >> 16) & 0xFF) as f32 + color.0 * k * 255.0;
+    let g = ((px >> 8) & 0xFF) as f32 + color.1 * k * 255.0;
+    let b = (px & 0xFF) as f32 + color.2 * k * 255.0;
+    renderer.color[idx] = pack(r, g, b);
+}
@@ -0,0 +1,78 @@
+use nalgebra_glm as glm;
+use std::f32::consts::{PI, TAU};
+
+use crate::environment::Environment;
+use crate::shader::{FlowmapTexture, FragInput, Shader, TriInput, Uniforms};
+use crate::texture::{Texture, TextureFormat};
+
+// Horneado de un shader procedural a una textura equirectangular, con la
+// misma proyección esférica que usan los shaders (u = longitud, v = latitud,
+// norte arriba). Cada texel es el punto de la esfera unidad visto de frente
+// y con la misma luz oblicua: el patrón sale sin terminador ni borde, y sin
+// saturar el especular como pasaría con la luz de frente.
+
+pub struct BakeInputs<'a> {
+    pub time: f32,
+    pub flowmap: Option<&'a FlowmapTexture>,
+    pub environment: Option<&'a Environment>,
+}
+
+// Punto de la esfera unidad para unas coordenadas esféricas (inversa de `spherical_uv`)
+fn sphere_point(u: f32, v: f32) -> glm::Vec3 {
+    let (phi, theta) = ((u - 0.5) * TAU, (v - 0.5) * PI);
+    glm::vec3(theta.cos() * phi.cos(), theta.sin(), theta.cos() * phi.sin())
+}
+
+pub fn bake_sphere(shader: &dyn Shader, inputs: &BakeInputs, width: usize, height: usize) -> Texture {
+    let uniforms = Uniforms {
+        base_color: (80, 100, 140),
+        light_dir: -glm::normalize(&glm::vec3(-0.4, 0.8, 0.7)),
+        ambient: 0.20,
+        spec_power: 50.0,
+        spec_strength: 0.45,
+        rim_strength: 0.30,
+        roughness: 0.35,
+        time: inputs.time,
+        flowmap: inputs.flowmap,
+        normal_map: None,
+        rings: None,
+        environment: inputs.environment,
+        view_to_world: glm::Mat3::identity(),
+        object_origin: glm::Vec3::zeros(),
+        object_scale: 1.0,
+    };
+    let facing = glm::vec3(0.0, 0.0, 1.0);
+    let (du, dv) = (1.0 / width as f32, 1.0 / height as f32);
+
+    Texture::from_fn(width, height, TextureFormat::Rgba8, |x, y| {
+        let uv = glm::vec2((x as f32 + 0.5) * du, 1.0 - (y as f32 + 0.5) * dv);
+        let p = sphere_point(uv.x, uv.y);
+        let tri = TriInput {
+            p0: p, p1: p, p2: p,
+            n0: facing, n1: facing, n2: facing,
+            uv0: uv, uv1: uv, uv2: uv,
+            t0: glm::Vec4::zeros(), t1: glm::Vec4::zeros(), t2: glm::Vec4::zeros(),
+            c0: glm::vec3(1.0, 1.0, 1.0), c1: glm::vec3(1.0, 1.0, 1.0), c2: glm::vec3(1.0, 1.0, 1.0),
+        };
+        let (r, g, b, a) = if shader.per_fragment(&uniforms) {
+            // Derivadas por texel: un texel a la derecha y otro hacia abajo
+            let frag = FragInput {
+                pos: p,
+                normal: facing,
+                uv,
+                tangent: glm::Vec3::zeros(),
+                bitangent: glm::Vec3::zeros(),
+                color: glm::vec3(1.0, 1.0, 1.0),
+                uv_dx: glm::vec2(du, 0.0),
+                uv_dy: glm::vec2(0.0, -dv),
+                pos_dx: sphere_point(uv.x + du, uv.y) - p,
+                pos_dy: sphere_point(uv.x, uv.y - dv) - p,
+            };
+            shader.shade_fragment(&uniforms, &tri, &frag)
+        } else {
+            let (r, g, b) = shader.shade(&uniforms, &tri);
+            (r, g, b, 255)
+        };
+        [r, g, b, a].map(|c| c as f32 / 255.0)
+    })
+}
@@ -0,0 +1,147 @@
+use clap::{Args, Parser, Subcommand};
+
+use crate::scene::ShaderKind;
+
+// Línea de órdenes. Sin subcomando se abre la ventana (las opciones de
+// `view` valen también sin escribirlo).
+
+#[derive(Parser)]
+#[command(version, about = "Renderer por software: sistema solar con shaders procedurales")]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub view: ViewArgs,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Ventana interactiva
+    View(ViewArgs),
+    /// Cuadros sin ventana a PNG
+    Render(RenderArgs),
+    /// Estadísticas y validación de un modelo
+    Info(InfoArgs),
+    /// Hornea un shader procedural a una textura equirectangular
+    Bake(BakeArgs),
+}
+
+// Qué escena se carga y qué se cambia de ella
+#[derive(Args, Clone)]
+pub struct SceneArgs {
+    /// Archivo de escena (.ron, .json o .toml)
+    #[arg(long, default_value = "assets/scene.ron")]
+    pub scene: String,
+    /// Modelo que sustituye a las mallas de archivo de la escena
+    #[arg(long)]
+    pub model: Option<String>,
+    /// Cuerpo enfocado: su nombre o su número (desde 1)
+    #[arg(long)]
+    pub focus: Option<String>,
+    /// Shader del cuerpo enfocado
+    #[arg(long, value_enum)]
+    pub shader: Option<ShaderKind>,
+}
+
+#[derive(Args, Clone, Copy)]
+pub struct Resolution {
+    /// Ancho en píxeles
+    #[arg(long, default_value_t = 900, value_parser = clap::value_parser!(u32).range(16..))]
+    pub width: u32,
+    /// Alto en píxeles
+    #[arg(long, default_value_t = 700, value_parser = clap::value_parser!(u32).range(16..))]
+    pub height: u32,
+}
+
+#[derive(Args, Clone)]
+pub struct ViewArgs {
+    #[command(flatten)]
+    pub scene: SceneArgs,
+    #[command(flatten)]
+    pub resolution: Resolution,
+    /// Segundos de animación al abrir
+    #[arg(long, default_value_t = 0.0)]
+    pub time: f32,
+}
+
+#[derive(Args)]
+pub struct RenderArgs {
+    #[command(flatten)]
+    pub scene: SceneArgs,
+    #[command(flatten)]
+    pub resolution: Resolution,
+    /// Segundos de animación del primer cuadro
+    #[arg(long, default_value_t = 0.0)]
+    pub time: f32,
+    /// Número de cuadros (con más de uno se numeran: render_0000.png, …)
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub frames: u32,
+    /// Cuadros por segundo de animación
+    #[arg(long, default_value_t = 30.0, value_parser = positive_f32)]
+    pub fps: f32,
+    /// Dibujar los anillos
+    #[arg(long)]
+    pub rings: bool,
+    /// Archivo de salida (PNG)
+    #[arg(short, long, default_value = "render.png")]
+    pub output: String,
+}
+
+// Número finito y mayor que cero (clap solo trae rangos para enteros)
+fn positive_f32(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(v) if v.is_finite() && v > 0.0 => Ok(v),
+        Ok(_) => Err("debe ser un número mayor que 0".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[derive(Args)]
+pub struct InfoArgs {
+    /// Modelo (.obj, .gltf, .glb, .stl o .ply)
+    pub model: String,
+    /// Mostrar también lo que quedaría tras repararlo
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(Args)]
+pub struct BakeArgs {
+    /// Shader procedural a hornear
+    #[arg(long, value_enum)]
+    pub shader: ShaderKind,
+    /// Parámetros de los shaders
+    #[arg(long, default_value = "assets/shaders.ron")]
+    pub params: String,
+    /// Ancho de la textura (el alto es la mitad)
+    #[arg(long, default_value_t = 1024, value_parser = clap::value_parser!(u32).range(2..))]
+    pub width: u32,
+    /// Segundos de animación
+    #[arg(long, default_value_t = 0.0)]
+    pub time: f32,
+    /// Archivo de salida (PNG)
+    #[arg(short, long, default_value = "bake.png")]
+    pub output: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_fps(fps: &str) -> Result<f32, clap::Error> {
+        let cli = Cli::try_parse_from(["modelo_nave_rs", "render", "--fps", fps])?;
+        match cli.command {
+            Some(Command::Render(args)) => Ok(args.fps),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fps_must_be_positive() {
+        assert_eq!(render_fps("24").unwrap(), 24.0);
+        for bad in ["0", "-30", "inf", "NaN", "abc"] {
+            assert!(render_fps(bad).is_err(), "--fps {} aceptado", bad);
+        }
+    }
+}
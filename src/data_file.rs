@@ -0,0 +1,16 @@
+use serde::de::DeserializeOwned;
+use std::path::Path;
+
+// Lee un archivo de datos (escena, parámetros de shaders…) eligiendo el
+// formato por la extensión: RON, JSON o TOML
+pub fn load<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Error leyendo '{}': {}", path, e))?;
+    let ext = Path::new(path).extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+    let parsed = match ext.as_deref() {
+        Some("ron") => ron::from_str(&text).map_err(|e| e.to_string()),
+        Some("json") => serde_json::from_str(&text).map_err(|e| e.to_string()),
+        Some("toml") => toml::from_str(&text).map_err(|e| e.to_string()),
+        _ => return Err(format!("'{}': se esperaba .ron, .json o .toml", path)),
+    };
+    parsed.map_err(|e| format!("Error en '{}': {}", path, e))
+}
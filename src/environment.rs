@@ -0,0 +1,130 @@
+use nalgebra_glm as glm;
+
+use crate::background::Background;
+
+// Un nivel del mapa de entorno equirectangular (radiancia lineal [0,1+])
+struct EnvLevel {
+    width: usize,
+    height: usize,
+    texels: Vec<glm::Vec3>,
+}
+
+impl EnvLevel {
+    fn texel(&self, x: i32, y: i32) -> glm::Vec3 {
+        let x = x.rem_euclid(self.width as i32) as usize;               // repetir en longitud
+        let y = y.clamp(0, self.height as i32 - 1) as usize;            // recortar en latitud
+        self.texels[y * self.width + x]
+    }
+
+    fn sample(&self, u: f32, v: f32) -> glm::Vec3 {
+        let fx = u * self.width as f32 - 0.5;
+        let fy = v * self.height as f32 - 0.5;
+        let x0 = fx.floor();
+        let y0 = fy.floor();
+        let tx = fx - x0;
+        let ty = fy - y0;
+        let (x0, y0) = (x0 as i32, y0 as i32);
+
+        let top = self.texel(x0, y0) * (1.0 - tx) + self.texel(x0 + 1, y0) * tx;
+        let bottom = self.texel(x0, y0 + 1) * (1.0 - tx) + self.texel(x0 + 1, y0 + 1) * tx;
+        top * (1.0 - ty) + bottom * ty
+    }
+
+    // Siguiente nivel: mitad de resolución + desenfoque separable
+    fn downsample_blur(&self) -> EnvLevel {
+        let w = (self.width / 2).max(4);
+        let h = (self.height / 2).max(2);
+        let mut half = vec![glm::Vec3::zeros(); w * h];
+        for y in 0..h {
+            for x in 0..w {
+                let sx = (x * 2) as i32;
+                let sy = (y * 2) as i32;
+                half[y * w + x] = (self.texel(sx, sy) + self.texel(sx + 1, sy)
+                    + self.texel(sx, sy + 1) + self.texel(sx + 1, sy + 1)) * 0.25;
+            }
+        }
+        let level = EnvLevel { width: w, height: h, texels: half };
+
+        // Núcleo binomial 1-4-6-4-1 en X y luego en Y
+        let k = [1.0, 4.0, 6.0, 4.0, 1.0];
+        let mut tmp = vec![glm::Vec3::zeros(); w * h];
+        for y in 0..h {
+            for x in 0..w {
+                let mut acc = glm::Vec3::zeros();
+                for (i, wk) in k.iter().enumerate() {
+                    acc += level.texel(x as i32 + i as i32 - 2, y as i32) * *wk;
+                }
+                tmp[y * w + x] = acc / 16.0;
+            }
+        }
+        let level = EnvLevel { width: w, height: h, texels: tmp };
+        let mut out = vec![glm::Vec3::zeros(); w * h];
+        for y in 0..h {
+            for x in 0..w {
+                let mut acc = glm::Vec3::zeros();
+                for (i, wk) in k.iter().enumerate() {
+                    acc += level.texel(x as i32, y as i32 + i as i32 - 2) * *wk;
+                }
+                out[y * w + x] = acc / 16.0;
+            }
+        }
+        EnvLevel { width: w, height: h, texels: out }
+    }
+}
+
+// Mapa de entorno prefiltrado: el nivel 0 es nítido y cada nivel siguiente
+// está más desenfocado. La rugosidad elige (e interpola) el nivel.
+pub struct Environment {
+    levels: Vec<EnvLevel>,
+}
+
+fn dir_to_equirect(d: glm::Vec3) -> (f32, f32) {
+    let u = d.x.atan2(-d.z) / std::f32::consts::TAU + 0.5;
+    let v = d.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI;
+    (u, v)
+}
+
+fn equirect_to_dir(u: f32, v: f32) -> glm::Vec3 {
+    let phi = (u - 0.5) * std::f32::consts::TAU;
+    let theta = v * std::f32::consts::PI;
+    glm::vec3(theta.sin() * phi.sin(), theta.cos(), -theta.sin() * phi.cos())
+}
+
+impl Environment {
+    // Hornea el fondo (cielo + estrellas) a un equirect de `width` x `width/2`
+    pub fn from_background(bg: &Background, width: usize, levels: usize) -> Self {
+        let width = width.max(8);
+        let height = width / 2;
+        let mut texels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let d = equirect_to_dir((x as f32 + 0.5) / width as f32, (y as f32 + 0.5) / height as f32);
+                let (r, g, b) = bg.sample(d);
+                texels.push(glm::vec3(r, g, b));
+            }
+        }
+        for star in &bg.stars.stars {
+            let (u, v) = dir_to_equirect(star.dir);
+            let x = ((u * width as f32) as usize).min(width - 1);
+            let y = ((v * height as f32) as usize).min(height - 1);
+            texels[y * width + x] += glm::vec3(star.color.0, star.color.1, star.color.2) * star.brightness;
+        }
+
+        let mut chain = vec![EnvLevel { width, height, texels }];
+        while chain.len() < levels.max(1) {
+            let next = chain[chain.len() - 1].downsample_blur();
+            chain.push(next);
+        }
+        Self { levels: chain }
+    }
+
+    // Radiancia en una dirección del mundo; `roughness` en [0,1]
+    pub fn sample(&self, d: glm::Vec3, roughness: f32) -> glm::Vec3 {
+        let (u, v) = dir_to_equirect(glm::normalize(&d));
+        let lod = roughness.clamp(0.0, 1.0).sqrt() * (self.levels.len() - 1) as f32;
+        let l0 = lod.floor() as usize;
+        let l1 = (l0 + 1).min(self.levels.len() - 1);
+        let t = lod - l0 as f32;
+        self.levels[l0].sample(u, v) * (1.0 - t) + self.levels[l1].sample(u, v) * t
+    }
+}
@@ -0,0 +1,60 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+struct Watched {
+    path: PathBuf,
+    seen: Option<SystemTime>,    // Fecha de la versión cargada
+    pending: Option<SystemTime>, // Fecha nueva vista en la consulta anterior
+}
+
+// Vigila archivos comparando su fecha de modificación cada `interval`. Un
+// cambio se informa cuando la fecha se repite en dos consultas seguidas,
+// para no leer un archivo a medio escribir. Los que no existen se vigilan
+// igual y se informan al aparecer.
+pub struct FileWatcher {
+    files: Vec<Watched>,
+    interval: Duration,
+    last_poll: Instant,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl FileWatcher {
+    pub fn new(interval: Duration) -> Self {
+        Self { files: Vec::new(), interval, last_poll: Instant::now() }
+    }
+
+    pub fn watch(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        if self.files.iter().any(|f| f.path == path) {
+            return;
+        }
+        let seen = modified(&path);
+        self.files.push(Watched { path, seen, pending: None });
+    }
+
+    // Archivos que cambiaron desde la última vez (nada si aún no toca mirar)
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        if self.last_poll.elapsed() < self.interval {
+            return Vec::new();
+        }
+        self.last_poll = Instant::now();
+
+        let mut changed = Vec::new();
+        for f in &mut self.files {
+            let Some(now) = modified(&f.path) else { continue }; // borrado o sin permisos
+            if Some(now) == f.seen {
+                f.pending = None;
+            } else if Some(now) == f.pending {
+                f.seen = Some(now);
+                f.pending = None;
+                changed.push(f.path.clone());
+            } else {
+                f.pending = Some(now);
+            }
+        }
+        changed
+    }
+}
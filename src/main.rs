@@ -1,19 +1,59 @@
+mod background;
+mod bake;
+mod cli;
+mod data_file;
+mod environment;
+mod file_watch;
+mod material;
 mod mesh;
+mod mesh_bounds;
+mod mesh_export;
+mod mesh_gltf;
+mod mesh_lod;
+mod mesh_normals;
+mod mesh_ply;
+mod mesh_primitives;
+mod mesh_simplify;
+mod mesh_stl;
+mod mesh_tangents;
+mod mesh_validate;
+mod mesh_weld;
+mod normal_map;
 mod raster;
 mod raster_z;
+mod render;
+mod scene;
+mod scene_view;
 mod shader;
-
+mod shader_atmosphere;
+mod shader_params;
+mod shader_pbr;
+mod shader_ring;
+mod shader_textured;
+mod texture;
+
+use clap::Parser;
 use minifb::{Key, KeyRepeat, Window, WindowOptions};
 use nalgebra_glm as glm;
-use image::{self, GenericImageView}; // para guardar PNG y cargar texturas
-
-use mesh::Mesh;
-use raster::rgb;
-use raster_z::tri_fill_z;
-use shader::{Uniforms, TriInput, Shader, MetalLambert, SunShader, RockyPlanetShader, GasGiantShader, FlowmapTexture};
-
-const WIDTH: usize  = 900;
-const HEIGHT: usize = 700;
+use std::path::Path;
+
+use bake::BakeInputs;
+use cli::{BakeArgs, Cli, Command, InfoArgs, RenderArgs, SceneArgs, ViewArgs};
+use environment::Environment;
+use file_watch::FileWatcher;
+use mesh::{LoadOptions, Mesh};
+use mesh_validate::RepairOptions;
+use mesh_normals::NormalMode;
+use render::Renderer;
+use scene::{Scene, ShaderKind};
+use scene_view::{SceneView, ViewState};
+use shader::{FlowmapTexture, GasGiantShader, MetalLambert, RockyPlanetShader, Shader, SunShader};
+use shader_params::ShaderParams;
+
+// Cada cuánto se revisan los archivos de la escena para recargarlos
+const RELOAD_POLL_SECS: f32 = 0.5;
+
+const WINDOW_TITLE: &str = "OVNI Metálico – Metal Shader Avanzado";
 
 // Convierte el framebuffer ARGB (0xAARRGGBB) a PNG RGBA y guarda.
 fn save_png(path: &str, buf: &[u32], w: usize, h: usize) -> Result<(), String> {
@@ -31,211 +71,278 @@ fn save_png(path: &str, buf: &[u32], w: usize, h: usize) -> Result<(), String> {
     img.save(path).map_err(|e| e.to_string())
 }
 
-fn main() -> Result<(), String> {
-    let mut window = Window::new(
-        "OVNI Metálico – Metal Shader Avanzado",
-        WIDTH, HEIGHT,
-        WindowOptions::default(),
-    ).map_err(|e| e.to_string())?;
+// `info`: resumen del modelo, sus partes y el informe de validación. Los
+// cargadores rechazan lo que no se puede dibujar (p.ej. índices fuera de
+// rango); entonces el informe es el motivo del rechazo.
+fn run_info(args: &InfoArgs) -> Result<(), String> {
+    let mut mesh = match Mesh::load(&args.model, 1.0, &LoadOptions::default()) {
+        Ok(mesh) => mesh,
+        Err(e) => {
+            println!("{}: no se pudo cargar", args.model);
+            println!("  {}", e);
+            println!("  ✗ malla con problemas");
+            return Err(format!("'{}' no es un modelo válido", args.model));
+        }
+    };
+    println!("{}: {}", args.model, mesh.summary());
+    let size = mesh.bounds.max - mesh.bounds.min;
+    println!("Tamaño: {:.4} × {:.4} × {:.4} (radio {:.4})", size.x, size.y, size.z, mesh.bounds.radius);
+    for sub in &mesh.submeshes {
+        let material = sub.material.map_or("-", |i| mesh.materials[i].name.as_str());
+        println!("  parte '{}': {} triángulos, material '{}'", sub.name, sub.count, material);
+    }
+    println!("{}", mesh.validate());
+    if args.check {
+        let (dropped, flipped) = mesh.repair(&RepairOptions::default());
+        println!("Tras reparar ({} triángulos quitados, {} volteados):", dropped, flipped);
+        println!("{}", mesh.validate());
+    }
+    Ok(())
+}
 
-    let mut color_buf = vec![rgb(8,10,14); WIDTH * HEIGHT];
-    let mut depth_buf = vec![f32::INFINITY; WIDTH * HEIGHT];
-
-    // Cargar flowmap de Jupiter
-    let jupiter_img = image::open("Jupiter.png").map_err(|e| e.to_string())?;
-    let (tex_w, tex_h) = jupiter_img.dimensions();
-    let jupiter_rgba = jupiter_img.to_rgba8();
-    let flowmap_texture = FlowmapTexture {
-        width: tex_w as usize,
-        height: tex_h as usize,
-        data: jupiter_rgba.into_raw(),
+// Carga la escena con los cambios de la línea de órdenes; devuelve también el cuerpo enfocado
+fn load_scene(args: &SceneArgs) -> Result<(Scene, usize), String> {
+    let mut scene = Scene::load(&args.scene)?;
+    if let Some(path) = &args.model {
+        scene.replace_models(path);
+    }
+    let focus = match &args.focus {
+        Some(key) => key.parse::<usize>().ok()
+            .filter(|&n| (1..=scene.bodies.len()).contains(&n))
+            .map(|n| n - 1)
+            .or_else(|| scene.body_index(key))
+            .ok_or_else(|| format!("La escena no tiene el cuerpo '{}'", key))?,
+        None => scene.focus(),
     };
+    if let Some(kind) = args.shader {
+        scene.bodies[focus].shader = kind;
+    }
+    println!("✓ Escena {} ({} cuerpos)", args.scene, scene.bodies.len());
+    Ok((scene, focus))
+}
 
-    // Cargar ambos modelos
-    println!("Cargando model.obj...");
-    let mesh_ovni = Mesh::load_obj("assets/model.obj", (WIDTH.min(HEIGHT) as f32) * 0.48)?;
-    println!("✓ model.obj cargado");
-    
-    println!("Cargando sphere.obj...");
-    let mesh_sphere = Mesh::load_obj("assets/sphere.obj", (WIDTH.min(HEIGHT) as f32) * 0.48)?;
-    println!("✓ sphere.obj cargado");
-    
-    let mut current_shader_index: usize = 3;  // Júpiter por defecto
-    let mut current_mesh = &mesh_sphere;
-
-    // Estado
-    let mut angle_x: f32 = 0.0;
-    let mut angle_y: f32 = 0.0;
-    let mut view_scale: f32 = 1.0;
-    let mut ufo_scale_on: bool = false;
-    let mut cull_backfaces: bool = false;
-
-    // Luz y material (metal azul oscuro pulido)
-    let mut uniforms = Uniforms {
-        base_color: (80, 100, 140),       // Azul metálico oscuro
-        light_dir: glm::normalize(&glm::vec3(-0.4, 0.8, 0.35)),
-        ambient: 0.20,                     
-        spec_power: 50.0,                  
-        spec_strength: 0.45,               
-        rim_strength: 0.30,
-        time: 0.0,
-        flowmap: Some(&flowmap_texture),
+// `render`: cuadros sin ventana; con más de uno se numeran antes de la extensión
+fn run_render(args: &RenderArgs) -> Result<(), String> {
+    let (scene, focus) = load_scene(&args.scene)?;
+    let (width, height) = (args.resolution.width as usize, args.resolution.height as usize);
+    let mut renderer = Renderer::new(width, height);
+    let mut view_scene = SceneView::load(scene)?;
+    let mut view = ViewState::new(&view_scene.scene);
+    view.focus = focus;
+    view.rings = args.rings;
+
+    let output = Path::new(&args.output);
+    let stem = output.file_stem().and_then(|s| s.to_str()).unwrap_or("render");
+    let ext = output.extension().and_then(|s| s.to_str()).unwrap_or("png");
+    if let Some(dir) = output.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Error creando '{}': {}", dir.display(), e))?;
+    }
+    for frame in 0..args.frames {
+        let time = args.time + frame as f32 / args.fps;
+        view_scene.draw(&mut renderer, &view, time);
+        let path = if args.frames > 1 {
+            output.with_file_name(format!("{}_{:04}.{}", stem, frame, ext))
+        } else {
+            output.to_path_buf()
+        };
+        let path = path.to_string_lossy();
+        save_png(&path, &renderer.color, width, height).map_err(|e| format!("Error al guardar '{}': {}", path, e))?;
+        println!("PNG guardado: {} (t = {:.2} s)", path, time);
+    }
+    Ok(())
+}
+
+// `bake`: el shader sobre la esfera unidad, desplegado en una textura 2:1
+fn run_bake(args: &BakeArgs) -> Result<(), String> {
+    let params = ShaderParams::load(&args.params)?;
+    let flowmap = FlowmapTexture::load(scene_view::FLOWMAP_PATH)?;
+    let environment = Environment::from_background(&background::Background::new((8, 10, 14)), 512, 6);
+    let metal = MetalLambert { params: params.metal };
+    let sun = SunShader { params: params.sun };
+    let rocky = RockyPlanetShader { params: params.rocky };
+    let gas = GasGiantShader { params: params.gas_giant };
+    let shader: &dyn Shader = match args.shader {
+        ShaderKind::Metal => &metal,
+        ShaderKind::Sun => &sun,
+        ShaderKind::Rocky => &rocky,
+        ShaderKind::GasGiant => &gas,
+        kind => return Err(format!("{:?} no es un shader procedural", kind)),
     };
-    let shader = MetalLambert;
+    let inputs = BakeInputs { time: args.time, flowmap: Some(&flowmap), environment: Some(&environment) };
+    let width = args.width as usize;
+    let texture = bake::bake_sphere(shader, &inputs, width, width / 2);
+    texture.save(Path::new(&args.output))?;
+    println!("Textura guardada: {} ({}×{})", args.output, texture.width, texture.height);
+    Ok(())
+}
+
+// `view`: ventana interactiva
+fn run_view(args: &ViewArgs) -> Result<(), String> {
+    let (scene, focus) = load_scene(&args.scene)?;
+    let (width, height) = (args.resolution.width as usize, args.resolution.height as usize);
 
+    let mut window = Window::new(
+        WINDOW_TITLE,
+        width, height,
+        WindowOptions::default(),
+    ).map_err(|e| e.to_string())?;
+
+    let mut renderer = Renderer::new(width, height);
+    let mut view_scene = SceneView::load(scene)?;
+    let mut view = ViewState::new(&view_scene.scene);
+    view.focus = focus;
     let mut last = std::time::Instant::now();
     let start_time = std::time::Instant::now();
+    let mut last_title = start_time;
 
-    // Cachés por vértice (iniciar con capacidad máxima)
-    let max_verts = mesh_ovni.positions.len().max(mesh_sphere.positions.len());
-    let mut v_view:   Vec<glm::Vec3> = vec![glm::vec3(0.0,0.0,0.0); max_verts];
-    let mut v_screen: Vec<(f32,f32)> = vec![(0.0,0.0);             max_verts];
-    let mut v_znorm:  Vec<f32>       = vec![0.0;                   max_verts];
+    // Recarga en caliente: flowmap, modelos, esfera de los planetas y parámetros
+    let mut watcher = FileWatcher::new(std::time::Duration::from_secs_f32(RELOAD_POLL_SECS));
+    for path in view_scene.watched_files() {
+        watcher.watch(&path);
+    }
 
     while window.is_open() && !window.is_key_down(Key::Escape) {
         let now = std::time::Instant::now();
         let dt = (now - last).as_secs_f32();
-        let elapsed = (now - start_time).as_secs_f32();
+        let elapsed = args.time + (now - start_time).as_secs_f32();
         last = now;
 
         // Controles
-        if window.is_key_down(Key::A) { angle_y += 1.6 * dt; }
-        if window.is_key_down(Key::D) { angle_y -= 1.6 * dt; }
-        if window.is_key_down(Key::W) { angle_x += 1.0 * dt; }
-        if window.is_key_down(Key::S) { angle_x -= 1.0 * dt; }
-
-        if window.is_key_down(Key::Minus) { view_scale = (view_scale - 0.75 * dt).max(0.5); }
-        if window.is_key_down(Key::Equal) { view_scale = (view_scale + 0.75 * dt).min(2.0); }
-
-        if window.is_key_pressed(Key::C, KeyRepeat::No) { ufo_scale_on = !ufo_scale_on; }
-        if window.is_key_pressed(Key::B, KeyRepeat::No) { cull_backfaces = !cull_backfaces; }
-        if window.is_key_pressed(Key::R, KeyRepeat::No) {
-            angle_x = 0.0; angle_y = 0.0; view_scale = 1.0; ufo_scale_on = false;
+        if window.is_key_down(Key::A) { view.yaw += 1.6 * dt; }
+        if window.is_key_down(Key::D) { view.yaw -= 1.6 * dt; }
+        if window.is_key_down(Key::W) { view.pitch += 1.0 * dt; }
+        if window.is_key_down(Key::S) { view.pitch -= 1.0 * dt; }
+
+        if window.is_key_down(Key::Minus) { view.zoom = (view.zoom - 0.75 * dt).max(0.5); }
+        if window.is_key_down(Key::Equal) { view.zoom = (view.zoom + 0.75 * dt).min(2.0); }
+
+        if window.is_key_pressed(Key::C, KeyRepeat::No) { view.squash = !view.squash; }
+        if window.is_key_pressed(Key::B, KeyRepeat::No) { view.cull_backfaces = !view.cull_backfaces; }
+        if window.is_key_pressed(Key::N, KeyRepeat::No) {
+            view.rings = !view.rings;
+            println!("Anillos: {}", if view.rings { "sí" } else { "no" });
         }
-        
-        // Cambio de modelo y shader
-        if window.is_key_pressed(Key::Key1, KeyRepeat::No) { 
-            current_shader_index = 3;
-            current_mesh = &mesh_sphere;
-            println!("Modelo: Júpiter (Flowmap)");
+        if window.is_key_pressed(Key::M, KeyRepeat::No) {
+            view.atmosphere = !view.atmosphere;
+            println!("Atmósfera: {}", if view.atmosphere { "sí" } else { "no" });
         }
-        if window.is_key_pressed(Key::Key2, KeyRepeat::No) { 
-            current_shader_index = 1;
-            current_mesh = &mesh_sphere;
-            println!("Modelo: Sol");
+        if window.is_key_pressed(Key::H, KeyRepeat::No) {
+            view.normal_maps = !view.normal_maps;
+            println!("Mapas de normales: {}", if view.normal_maps { "sí" } else { "no" });
         }
-        if window.is_key_pressed(Key::Key3, KeyRepeat::No) { 
-            current_shader_index = 2;
-            current_mesh = &mesh_sphere;
-            println!("Modelo: Planeta Rocoso");
+        if window.is_key_pressed(Key::V, KeyRepeat::No) {
+            view.dome_visible = !view.dome_visible;
+            println!("Cúpula: {}", if view.dome_visible { "visible" } else { "oculta" });
         }
-        if window.is_key_pressed(Key::Key4, KeyRepeat::No) { 
-            current_shader_index = 0;
-            current_mesh = &mesh_ovni;
-            println!("Modelo: OVNI - Metal");
+        if window.is_key_pressed(Key::G, KeyRepeat::No) { view.dome_lifted = !view.dome_lifted; }
+        if window.is_key_pressed(Key::T, KeyRepeat::No) {
+            let shapes = &view_scene.planet_shapes;
+            view.planet_shape = (view.planet_shape + 1) % (shapes.len() + 1);
+            let name = view.planet_shape.checked_sub(1).map_or("esfera cargada", |i| shapes[i].0);
+            println!("Forma de los planetas: {}", name);
         }
 
-        uniforms.time = elapsed;
-
-        color_buf.fill(rgb(8,10,14));
-        depth_buf.fill(f32::INFINITY);
-
-        // Rotación automática lenta para planetas (todos excepto OVNI que es shader_idx 0)
-        let auto_rotation = if current_shader_index != 0 {
-            glm::rotation(elapsed * 0.15, &glm::vec3(0.0, 1.0, 0.0))
-        } else {
-            glm::identity()
-        };
-
-        // Rotación manual con WASD
-        let rot_y = glm::rotation(angle_y, &glm::vec3(0.0, 1.0, 0.0));
-        let rot_x = glm::rotation(angle_x, &glm::vec3(1.0, 0.0, 0.0));
-        let model = if ufo_scale_on {
-            let s = glm::scaling(&glm::vec3(1.10, 0.75, 1.10));
-            rot_y * rot_x * auto_rotation * s
-        } else {
-            rot_y * rot_x * auto_rotation
-        };
-
-        // PASS 1: transformar vértices y obtener z_min/z_max
-        let mut z_min = f32::INFINITY;
-        let mut z_max = f32::NEG_INFINITY;
-        for (i, v) in current_mesh.positions.iter().enumerate() {
-            let q = (model * glm::vec4(v.x, v.y, v.z, 1.0)).xyz();
-            v_view[i] = q;
-            z_min = z_min.min(q.z);
-            z_max = z_max.max(q.z);
-        }
-        if (z_max - z_min).abs() < 1e-9 { z_max = z_min + 1e-6; }
-        let nz = |z: f32| (z - z_min) / (z_max - z_min);
-
-        // PASS 2: proyectar a pantalla y normalizar Z (una vez por vértice)
-        for (i, q) in v_view[..current_mesh.positions.len()].iter().enumerate() {
-            let s = current_mesh.to_screen_scaled(*q, WIDTH, HEIGHT, view_scale);
-            v_screen[i] = (s.0 as f32, s.1 as f32);
-            v_znorm[i]  = nz(q.z);
-        }
-
-        // PASS 3: raster por triángulo
-        for f in &current_mesh.indices {
-            let i0 = f[0] as usize;
-            let i1 = f[1] as usize;
-            let i2 = f[2] as usize;
-
-            let q0 = v_view[i0];
-            let q1 = v_view[i1];
-            let q2 = v_view[i2];
-
-            if cull_backfaces {
-                let n = (q1 - q0).cross(&(q2 - q0));
-                if n.z >= 0.0 { continue; } // cámara mira -Z en este "espacio vista"
+        // Rugosidad de los reflejos del metal (o del material PBR si el foco lo usa)
+        let focus_pbr = view_scene.scene.bodies[view.focus].shader == ShaderKind::Pbr;
+        let roughness = if focus_pbr { &mut view_scene.pbr_shaders[view.focus].material.roughness } else { &mut view_scene.roughness };
+        if window.is_key_down(Key::LeftBracket)  { *roughness = (*roughness - 0.5 * dt).max(0.04); }
+        if window.is_key_down(Key::RightBracket) { *roughness = (*roughness + 0.5 * dt).min(1.0); }
+
+        // Metalicidad del material PBR
+        let metallic = &mut view_scene.pbr_shaders[view.focus].material.metallic;
+        if window.is_key_down(Key::Comma)  { *metallic = (*metallic - 0.5 * dt).max(0.0); }
+        if window.is_key_down(Key::Period) { *metallic = (*metallic + 0.5 * dt).min(1.0); }
+
+        if window.is_key_pressed(Key::R, KeyRepeat::No) { view.reset_camera(&view_scene.scene); }
+
+        // Cuerpo enfocado: teclas 1-9 en el orden de la escena
+        let number_keys = [Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9];
+        for (i, &key) in number_keys.iter().enumerate().take(view_scene.scene.bodies.len()) {
+            if window.is_key_pressed(key, KeyRepeat::No) {
+                view.focus = i;
+                println!("Foco: {}", view_scene.scene.bodies[i].name);
             }
+        }
 
-            // Normales transformadas por el modelo (sin traslación)
-            let n0 = (model * glm::vec4(current_mesh.normals[i0].x, current_mesh.normals[i0].y, current_mesh.normals[i0].z, 0.0)).xyz().normalize();
-            let n1 = (model * glm::vec4(current_mesh.normals[i1].x, current_mesh.normals[i1].y, current_mesh.normals[i1].z, 0.0)).xyz().normalize();
-            let n2 = (model * glm::vec4(current_mesh.normals[i2].x, current_mesh.normals[i2].y, current_mesh.normals[i2].z, 0.0)).xyz().normalize();
-
-            let tri_in = TriInput { p0: q0, p1: q1, p2: q2, n0, n1, n2 };
-            
-            // Seleccionar shader según el índice actual
-            let (r,g,b) = match current_shader_index {
-                0 => shader.shade(&uniforms, &tri_in),           // OVNI - Metal
-                1 => SunShader.shade(&uniforms, &tri_in),        // Sol
-                2 => RockyPlanetShader.shade(&uniforms, &tri_in), // Rocoso
-                3 => GasGiantShader.shade(&uniforms, &tri_in),   // Gaseoso
-                _ => shader.shade(&uniforms, &tri_in),
+        // Modo de normales de los modelos: pliegue → plana → suave (los recarga)
+        if window.is_key_pressed(Key::F, KeyRepeat::No) {
+            let mode = match view_scene.model_opts.normals {
+                NormalMode::Creased(_) => NormalMode::Flat,
+                NormalMode::Flat => NormalMode::Smooth,
+                NormalMode::Smooth => NormalMode::creased_degrees(40.0),
             };
-            
-            let color = rgb(r,g,b);
-
-            let s0 = v_screen[i0];
-            let s1 = v_screen[i1];
-            let s2 = v_screen[i2];
-
-            let v0 = (s0.0, s0.1, v_znorm[i0]);
-            let v1 = (s1.0, s1.1, v_znorm[i1]);
-            let v2 = (s2.0, s2.1, v_znorm[i2]);
+            view_scene.set_normal_mode(mode);
+            println!("Normales de los modelos: {:?}", mode);
+        }
 
-            tri_fill_z(color, &mut color_buf, &mut depth_buf, WIDTH, HEIGHT, [v0, v1, v2]);
+        for path in watcher.poll() {
+            match view_scene.reload(&path) {
+                Some(Ok(msg)) => println!("{}", msg),
+                Some(Err(e)) => eprintln!("{}", e),
+                None => {}
+            }
         }
 
+        view_scene.draw(&mut renderer, &view, elapsed);
+
         // Guardar PNG al presionar P
         if window.is_key_pressed(Key::P, KeyRepeat::No) {
             let ts = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
             let filename = format!("render_{}.png", ts);
-            if let Err(e) = save_png(&filename, &color_buf, WIDTH, HEIGHT) {
+            if let Err(e) = save_png(&filename, &renderer.color, width, height) {
                 eprintln!("Error al guardar PNG: {}", e);
             } else {
                 println!("PNG guardado: {}", filename);
             }
         }
 
+        // Exportar la malla del cuerpo enfocado a OBJ (con MTL si tiene materiales) al presionar X
+        if window.is_key_pressed(Key::X, KeyRepeat::No) {
+            let ts = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+            let filename = format!("export_{}.obj", ts);
+            let body = &view_scene.scene.bodies[view.focus];
+            let mesh = view_scene.body_mesh(view.focus, view.planet_shape);
+            match mesh.save_obj(&filename, &(body.scaling() * view.squash_matrix())) {
+                Ok(()) => println!("OBJ guardado: {}", filename),
+                Err(e) => eprintln!("Error al exportar OBJ: {}", e),
+            }
+            if let (true, Some(rings), Some(r)) = (view.rings, view_scene.body_rings(view.focus), body.rings) {
+                let filename = format!("export_{}_anillos.obj", ts);
+                let tilt = body.scaling() * glm::rotation(r.tilt.to_radians(), &glm::vec3(1.0, 0.0, 0.0));
+                match rings.save_obj(&filename, &tilt) {
+                    Ok(()) => println!("OBJ guardado: {}", filename),
+                    Err(e) => eprintln!("Error al exportar OBJ: {}", e),
+                }
+            }
+        }
+
+        // Estadísticas del cuadro en el título (dos veces por segundo)
+        if now.duration_since(last_title).as_secs_f32() >= 0.5 {
+            let st = renderer.stats;
+            window.set_title(&format!(
+                "{} | objetos {} ({} fuera) | clusters {} ({} fuera) | {} triángulos",
+                WINDOW_TITLE, st.objects, st.objects_culled, st.clusters, st.clusters_culled, st.triangles,
+            ));
+            last_title = now;
+        }
+
         // Presentar en pantalla
-        window.update_with_buffer(&color_buf, WIDTH, HEIGHT)
+        window.update_with_buffer(&renderer.color, width, height)
               .map_err(|e| e.to_string())?;
     }
 
     Ok(())
 }
+
+fn main() -> Result<(), String> {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::View(args)) => run_view(&args),
+        Some(Command::Render(args)) => run_render(&args),
+        Some(Command::Info(args)) => run_info(&args),
+        Some(Command::Bake(args)) => run_bake(&args),
+        None => run_view(&cli.view),
+    }
+}
@@ -0,0 +1,101 @@
+use nalgebra_glm as glm;
+use std::path::Path;
+
+use crate::normal_map;
+use crate::texture::Texture;
+
+// Parámetros metálico-rugosos (glTF). Con ellos el material se sombrea con
+// Cook-Torrance y `diffuse` es el color base en espacio lineal.
+#[derive(Clone)]
+pub struct PbrParams {
+    pub metallic: f32,
+    pub roughness: f32,
+    pub emissive: glm::Vec3,
+    pub metallic_roughness_map: Option<Texture>, // G = rugosidad, B = metalicidad
+}
+
+// Material de un MTL (modelo Blinn-Phong clásico) o de un glTF (con `pbr`).
+// Colores en [0,1]: sRGB en MTL, lineales en PBR.
+#[derive(Clone)]
+pub struct Material {
+    pub name: String,
+    pub diffuse: glm::Vec3,                    // Kd / baseColorFactor
+    pub specular: glm::Vec3,                   // Ks
+    pub shininess: f32,                        // Ns
+    pub opacity: f32,                          // d (1 = opaco)
+    pub diffuse_map: Option<Texture>,   // map_Kd / baseColorTexture (sRGB)
+    pub normal_map: Option<Texture>,    // map_Bump / bump (alturas o normales)
+    pub pbr: Option<PbrParams>,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            name: String::from("default"),
+            diffuse: glm::vec3(0.7, 0.7, 0.7),
+            specular: glm::vec3(0.3, 0.3, 0.3),
+            shininess: 32.0,
+            opacity: 1.0,
+            diffuse_map: None,
+            normal_map: None,
+            pbr: None,
+        }
+    }
+}
+
+// Opciones de map_Bump: "-bm <escala> archivo.png" (el archivo va al final)
+fn parse_bump(spec: &str) -> (f32, &str) {
+    let words: Vec<&str> = spec.split_whitespace().collect();
+    let strength = words
+        .iter()
+        .position(|w| *w == "-bm")
+        .and_then(|i| words.get(i + 1))
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(1.0);
+    (strength, words.last().copied().unwrap_or(""))
+}
+
+// Un map_Bump en escala de grises es un mapa de alturas; si tiene color se
+// asume que ya es un mapa de normales en espacio tangente.
+fn bump_to_normal_map(tex: Texture, strength: f32) -> Texture {
+    let texels: Vec<glm::Vec4> = (0..tex.height).flat_map(|y| (0..tex.width).map(move |x| (x, y))).map(|(x, y)| tex.texel(x, y)).collect();
+    let gray = tex.format.channels() == 1 || texels.iter().all(|c| c.x == c.y && c.y == c.z);
+    if !gray {
+        return tex;
+    }
+    let heights: Vec<f32> = texels.iter().map(|c| c.x * 4.0).collect();
+    normal_map::from_heights(tex.width, tex.height, &heights, strength)
+}
+
+impl Material {
+    // Convierte un material de tobj; las texturas se buscan junto al OBJ.
+    // Una textura que no carga se informa y se omite.
+    pub fn from_tobj(m: &tobj::Material, base_dir: &Path) -> Self {
+        let defaults = Material::default();
+        let vec3_or = |v: Option<[f32; 3]>, d: glm::Vec3| v.map_or(d, |c| glm::vec3(c[0], c[1], c[2]));
+        let load = |file: &str| {
+            let path = base_dir.join(file);
+            Texture::load(&path.to_string_lossy())
+                .map_err(|e| eprintln!("Material '{}': {}", m.name, e))
+                .ok()
+        };
+
+        Self {
+            name: m.name.clone(),
+            diffuse: vec3_or(m.diffuse, defaults.diffuse),
+            specular: vec3_or(m.specular, defaults.specular),
+            shininess: m.shininess.unwrap_or(defaults.shininess).max(1.0),
+            opacity: m.dissolve.unwrap_or(1.0).clamp(0.0, 1.0),
+            diffuse_map: m.diffuse_texture.as_deref().and_then(load),
+            normal_map: m.normal_texture.as_deref().and_then(|spec| {
+                let (strength, file) = parse_bump(spec);
+                load(file).map(|tex| bump_to_normal_map(tex, strength))
+            }),
+            pbr: None,
+        }
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        self.opacity < 1.0
+    }
+}
@@ -1,30 +1,110 @@
 use nalgebra_glm as glm;
+use std::collections::HashMap;
 use std::path::Path;
 
+use crate::material::Material;
+use crate::mesh_bounds::{Bounds, Cluster};
+use crate::mesh_normals::NormalMode;
+use crate::mesh_weld::WeldOptions;
+
+// Parte con nombre (objeto/grupo del OBJ): rango de triángulos de `indices`
+// con su material. Cada parte tiene vértices propios, así que puede
+// ocultarse o moverse por separado.
+#[derive(Clone, Debug)]
+pub struct SubMesh {
+    pub name: String,
+    pub start: usize,
+    pub count: usize,
+    pub material: Option<usize>,  // Índice en `materials` (None = material por defecto)
+    pub visible: bool,
+    pub transform: glm::Mat4,     // Se aplica antes de la matriz del modelo
+}
+
+impl SubMesh {
+    pub fn new(name: &str, start: usize, count: usize, material: Option<usize>) -> Self {
+        Self { name: name.to_string(), start, count, material, visible: true, transform: glm::identity() }
+    }
+}
+
+#[derive(Clone)]
 pub struct Mesh {
     pub positions: Vec<glm::Vec3>,
     pub normals:   Vec<glm::Vec3>,  // SMOOTH NORMALS por vértice
+    pub uvs:       Vec<glm::Vec2>,  // Coordenadas de textura por vértice (vacío si el archivo no trae)
+    pub tangents:  Vec<glm::Vec4>,  // Tangente + signo de la bitangente (vacío sin UVs)
+    pub colors:    Vec<glm::Vec3>,  // Color por vértice en [0,1] (vacío si el archivo no trae)
     pub indices:   Vec<[u32; 3]>,
+    pub submeshes: Vec<SubMesh>,    // Partes por objeto/grupo y material, en orden de `indices`
+    pub materials: Vec<Material>,   // Materiales del MTL (vacío si no hay)
+    pub bounds:    Bounds,          // Caja y esfera envolventes de `positions`
+    pub part_bounds: Vec<Bounds>,   // Las mismas por parte, en orden de `submeshes`
+    pub clusters:  Vec<Cluster>,    // Grupos de triángulos con sus límites (opcional)
     pub center:    glm::Vec3,
     pub scale:     f32,
 }
 
+// Opciones de carga
+#[derive(Clone, Copy, Debug)]
+pub struct LoadOptions {
+    pub keep_file_normals: bool,  // Usar las normales del archivo si están completas
+    pub normals: NormalMode,      // Generación cuando faltan (o si no se conservan)
+    pub weld: Option<WeldOptions>, // Unir vértices duplicados antes de las normales
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self { keep_file_normals: true, normals: NormalMode::creased_degrees(60.0), weld: None }
+    }
+}
+
 impl Mesh {
+    // Elige el cargador según la extensión del archivo
+    pub fn load(path: &str, target_pixels: f32, opts: &LoadOptions) -> Result<Self, String> {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("obj") => Self::load_obj_with(path, target_pixels, opts),
+            Some("gltf") | Some("glb") => Self::load_gltf_with(path, target_pixels, opts),
+            Some("stl") => Self::load_stl_with(path, target_pixels, opts),
+            Some("ply") => Self::load_ply_with(path, target_pixels, opts),
+            _ => Err(format!("Formato de modelo no soportado: '{}'", path)),
+        }
+    }
+
     pub fn load_obj(path: &str, target_pixels: f32) -> Result<Self, String> {
+        Self::load_obj_with(path, target_pixels, &LoadOptions::default())
+    }
+
+    pub fn load_obj_with(path: &str, target_pixels: f32, opts: &LoadOptions) -> Result<Self, String> {
         let obj_options = tobj::LoadOptions {
             triangulate: true,
             single_index: false,
             ..Default::default()
         };
-        let (models, _materials) = tobj::load_obj(
+        let (models, materials) = tobj::load_obj(
             Path::new(path),
             &obj_options,
         ).map_err(|e| format!("Error cargando '{}': {}", path, e))?;
 
+        // Un MTL ausente o inválido no impide cargar la geometría
+        let base_dir = Path::new(path).parent().unwrap_or(Path::new("."));
+        let materials: Vec<Material> = match materials {
+            Ok(ms) => ms.iter().map(|m| Material::from_tobj(m, base_dir)).collect(),
+            Err(e) => {
+                eprintln!("Materiales de '{}' no disponibles: {}", path, e);
+                Vec::new()
+            }
+        };
+        let mut submeshes: Vec<SubMesh> = Vec::new();
+
         let mut positions: Vec<glm::Vec3> = Vec::new();
         let mut normals:   Vec<glm::Vec3> = Vec::new();
+        let mut uvs:       Vec<glm::Vec2> = Vec::new();
         let mut indices:   Vec<[u32; 3]>  = Vec::new();
-        let mut base: u32 = 0;
+        let mut any_uvs = false;
+        let mut all_normals = true;
 
         for m in models {
             let mesh = m.mesh;
@@ -32,75 +112,182 @@ impl Mesh {
             if mesh.positions.len() % 3 != 0 {
                 return Err(format!("Posiciones inválidas en {}", path));
             }
-            
-            let num_verts = mesh.positions.len() / 3;
-            
-            // Cargar posiciones
-            for i in (0..mesh.positions.len()).step_by(3) {
-                positions.push(glm::vec3(
-                    mesh.positions[i],
-                    mesh.positions[i + 1],
-                    mesh.positions[i + 2],
-                ));
-            }
-            
-            // Generar normales smooth desde posiciones
-            // Para geometría esférica: normal = position.normalize()
-            for pos in &positions[positions.len() - num_verts..] {
-                normals.push(pos.normalize());
-            }
-            
-            // Cargar índices
             if mesh.indices.len() % 3 != 0 {
                 return Err(format!("Índices no triangulados en {}", path));
             }
             
-            for i in (0..mesh.indices.len()).step_by(3) {
-                indices.push([
-                    base + mesh.indices[i] as u32,
-                    base + mesh.indices[i + 1] as u32,
-                    base + mesh.indices[i + 2] as u32,
-                ]);
+            let num_pos = mesh.positions.len() / 3;
+            let num_nrm = mesh.normals.len() / 3;
+            let num_uv  = mesh.texcoords.len() / 2;
+
+            // Con single_index = false cada esquina trae índices separados de
+            // posición/normal/UV; solo se usan si cubren todas las esquinas.
+            let has_normals = num_nrm > 0 && mesh.normal_indices.len() == mesh.indices.len();
+            let has_uvs     = num_uv > 0 && mesh.texcoord_indices.len() == mesh.indices.len();
+            any_uvs |= has_uvs;
+            all_normals &= has_normals;
+
+            // Un vértice por combinación única (posición, normal, uv)
+            let mut remap: HashMap<(u32, u32, u32), u32> = HashMap::new();
+            let mut corners: Vec<u32> = Vec::with_capacity(mesh.indices.len());
+
+            for (k, &pi) in mesh.indices.iter().enumerate() {
+                let ni = if has_normals { mesh.normal_indices[k] } else { u32::MAX };
+                let ti = if has_uvs { mesh.texcoord_indices[k] } else { u32::MAX };
+                if pi as usize >= num_pos
+                    || (has_normals && ni as usize >= num_nrm)
+                    || (has_uvs && ti as usize >= num_uv)
+                {
+                    return Err(format!("Índice fuera de rango en {}", path));
+                }
+
+                let id = *remap.entry((pi, ni, ti)).or_insert_with(|| {
+                    let p = pi as usize * 3;
+                    positions.push(glm::vec3(mesh.positions[p], mesh.positions[p + 1], mesh.positions[p + 2]));
+                    normals.push(if has_normals {
+                        let n = ni as usize * 3;
+                        let v = glm::vec3(mesh.normals[n], mesh.normals[n + 1], mesh.normals[n + 2]);
+                        if v.magnitude() > 1e-12 { v.normalize() } else { v }
+                    } else {
+                        glm::Vec3::zeros()
+                    });
+                    uvs.push(if has_uvs {
+                        let t = ti as usize * 2;
+                        glm::vec2(mesh.texcoords[t], mesh.texcoords[t + 1])
+                    } else {
+                        glm::Vec2::zeros()
+                    });
+                    positions.len() as u32 - 1
+                });
+                corners.push(id);
+            }
+
+            let start = indices.len();
+            for c in corners.chunks_exact(3) {
+                indices.push([c[0], c[1], c[2]]);
+            }
+            if indices.len() > start {
+                let material = mesh.material_id.filter(|&id| id < materials.len());
+                submeshes.push(SubMesh::new(&m.name, start, indices.len() - start, material));
             }
-            base += num_verts as u32;
+        }
+
+        if !any_uvs {
+            uvs.clear();
         }
 
         if positions.is_empty() || indices.is_empty() {
             return Err("OBJ vacío o sin triángulos".into());
         }
 
-        // bounding box → centro y escala a target_pixels
-        let mut minv = positions[0];
-        let mut maxv = positions[0];
-        for p in &positions {
-            minv = glm::min2(&minv, p);
-            maxv = glm::max2(&maxv, p);
+        let mut mesh = Self::new(positions, normals, uvs, indices, target_pixels);
+        mesh.submeshes = submeshes;
+        mesh.materials = materials;
+        mesh.compute_bounds();
+        if let Some(weld) = &opts.weld {
+            mesh.weld_vertices(weld);
         }
-        let center = (minv + maxv) * 0.5;
-        let size   = maxv - minv;
+
+        // Normales incompletas en el archivo (o no deseadas): generarlas
+        if !all_normals || !opts.keep_file_normals {
+            mesh.compute_normals(opts.normals);
+        }
+
+        // Marco tangente para mapas de normales (solo si hay UVs)
+        if !mesh.uvs.is_empty() {
+            mesh.compute_tangents().map_err(|e| format!("Error en tangentes de '{}': {}", path, e))?;
+        }
+
+        Ok(mesh)
+    }
+
+    // Construye una malla ya triangulada y calcula centro/escala a target_pixels
+    pub fn new(
+        positions: Vec<glm::Vec3>,
+        normals: Vec<glm::Vec3>,
+        uvs: Vec<glm::Vec2>,
+        indices: Vec<[u32; 3]>,
+        target_pixels: f32,
+    ) -> Self {
+        let submeshes = vec![SubMesh::new("mesh", 0, indices.len(), None)];
+        let mut mesh = Self {
+            positions, normals, uvs, tangents: Vec::new(), colors: Vec::new(), indices, submeshes,
+            materials: Vec::new(), bounds: Bounds::from_points(std::iter::empty()), part_bounds: Vec::new(),
+            clusters: Vec::new(),
+            center: glm::Vec3::zeros(), scale: 1.0,
+        };
+        mesh.fit_to(target_pixels);
+        mesh
+    }
+
+    // Recalcula centro y escala para que la malla mida target_pixels en pantalla
+    pub fn fit_to(&mut self, target_pixels: f32) {
+        // bounding box → centro y escala a target_pixels
+        self.compute_bounds();
+        let size    = self.bounds.max - self.bounds.min;
         let max_dim = size.x.max(size.y).max(size.z).max(1e-6);
-        let scale   = target_pixels / max_dim;
+        self.center = self.bounds.center;
+        self.scale  = target_pixels / max_dim;
+    }
+
+    // Reemplaza los atributos por vértice por los de `sources[i]` (para partir
+    // o fusionar vértices). Los índices los actualiza quien llama.
+    pub(crate) fn reindex_vertices(&mut self, sources: &[u32]) {
+        self.positions = sources.iter().map(|&i| self.positions[i as usize]).collect();
+        self.normals = sources.iter().map(|&i| self.normals[i as usize]).collect();
+        if !self.uvs.is_empty() {
+            self.uvs = sources.iter().map(|&i| self.uvs[i as usize]).collect();
+        }
+        if !self.tangents.is_empty() {
+            self.tangents = sources.iter().map(|&i| self.tangents[i as usize]).collect();
+        }
+        if !self.colors.is_empty() {
+            self.colors = sources.iter().map(|&i| self.colors[i as usize]).collect();
+        }
+    }
+
+    // Parte a la que pertenece cada vértice (la primera que lo usa;
+    // `usize::MAX` si ninguna). Al unir vértices solo se comparan los de
+    // la misma parte, para que cada una siga teniendo los suyos.
+    pub(crate) fn vertex_parts(&self) -> Vec<usize> {
+        let mut parts = vec![usize::MAX; self.positions.len()];
+        for (p, sub) in self.submeshes.iter().enumerate() {
+            for t in &self.indices[sub.start..sub.start + sub.count] {
+                for &v in t {
+                    let owner = &mut parts[v as usize];
+                    if *owner == usize::MAX {
+                        *owner = p;
+                    }
+                }
+            }
+        }
+        parts
+    }
 
-        Ok(Self { positions, normals, indices, center, scale })
+    // Índice de la primera parte con ese nombre
+    pub fn submesh(&self, name: &str) -> Option<usize> {
+        self.submeshes.iter().position(|s| s.name == name)
     }
 
-    #[inline]
-    pub fn to_screen(&self, v: glm::Vec3, width: usize, height: usize) -> (i32, i32) {
-        let hw = (width as f32) * 0.5;
-        let hh = (height as f32) * 0.5;
-        let sx = (v.x - self.center.x) * self.scale + hw;
-        let sy = hh - ((v.y - self.center.y) * self.scale);
-        (sx.round() as i32, sy.round() as i32)
+    // Resumen corto para la consola
+    pub fn summary(&self) -> String {
+        format!(
+            "{} vértices, {} triángulos, {} partes, {} materiales, UVs: {}, tangentes: {}, colores: {}",
+            self.positions.len(),
+            self.indices.len(),
+            self.submeshes.len(),
+            self.materials.len(),
+            if self.uvs.is_empty() { "no" } else { "sí" },
+            if self.tangents.is_empty() { "no" } else { "sí" },
+            if self.colors.is_empty() { "no" } else { "sí" },
+        )
     }
 
-    // Variante con multiplicador de escala en vista
-    #[inline]
-    pub fn to_screen_scaled(&self, v: glm::Vec3, width: usize, height: usize, scale_mul: f32) -> (i32, i32) {
-        let hw = (width as f32) * 0.5;
-        let hh = (height as f32) * 0.5;
-        let s  = self.scale * scale_mul;
-        let sx = (v.x - self.center.x) * s + hw;
-        let sy = hh - ((v.y - self.center.y) * s);
-        (sx.round() as i32, sy.round() as i32)
+    // Distancia máxima de un vértice al centro (radio envolvente)
+    pub fn radius(&self) -> f32 {
+        self.positions
+            .iter()
+            .map(|p| (p - self.center).magnitude())
+            .fold(0.0, f32::max)
     }
 }
@@ -0,0 +1,95 @@
+use nalgebra_glm as glm;
+
+use crate::mesh::Mesh;
+
+// Caja alineada a los ejes y esfera envolvente (centrada en la caja)
+#[derive(Clone, Copy, Debug)]
+pub struct Bounds {
+    pub min: glm::Vec3,
+    pub max: glm::Vec3,
+    pub center: glm::Vec3,
+    pub radius: f32,
+}
+
+impl Bounds {
+    pub fn from_points<'a>(points: impl Iterator<Item = &'a glm::Vec3> + Clone) -> Self {
+        let mut min = glm::vec3(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+        let mut max = -min;
+        for p in points.clone() {
+            min = glm::min2(&min, p);
+            max = glm::max2(&max, p);
+        }
+        if min.x > max.x {
+            return Self { min: glm::Vec3::zeros(), max: glm::Vec3::zeros(), center: glm::Vec3::zeros(), radius: 0.0 };
+        }
+        let center = (min + max) * 0.5;
+        let radius = points.map(|p| (p - center).magnitude()).fold(0.0, f32::max);
+        Self { min, max, center, radius }
+    }
+}
+
+// Grupo de triángulos contiguos en `indices` (dentro de una sola parte)
+#[derive(Clone, Debug)]
+pub struct Cluster {
+    pub start: usize,
+    pub count: usize,
+    pub bounds: Bounds,
+}
+
+// Intercala los 10 bits bajos de x, y, z (orden Z)
+fn morton(q: [u32; 3]) -> u32 {
+    let spread = |mut v: u32| {
+        v &= 0x3ff;
+        v = (v | (v << 16)) & 0x0300_00ff;
+        v = (v | (v << 8)) & 0x0300_f00f;
+        v = (v | (v << 4)) & 0x030c_30c3;
+        (v | (v << 2)) & 0x0924_9249
+    };
+    spread(q[0]) | (spread(q[1]) << 1) | (spread(q[2]) << 2)
+}
+
+impl Mesh {
+    // Límites de la malla entera y de cada parte (con sus propios vértices;
+    // los índices fuera de rango se ignoran, los cuenta `validate`)
+    pub fn compute_bounds(&mut self) {
+        self.bounds = Bounds::from_points(self.positions.iter());
+        self.part_bounds = self.submeshes.iter().map(|sub| {
+            let points: Vec<glm::Vec3> = self.indices[sub.start..sub.start + sub.count]
+                .iter().flatten().filter_map(|&i| self.positions.get(i as usize).copied()).collect();
+            Bounds::from_points(points.iter())
+        }).collect();
+    }
+
+    // Agrupa los triángulos de cada parte en clusters de hasta `max_triangles`.
+    // Antes los ordena por la curva Z de su centroide para que cada cluster
+    // sea compacto (el orden de dibujo dentro de la parte cambia).
+    pub fn build_clusters(&mut self, max_triangles: usize) {
+        let max_triangles = max_triangles.max(1);
+        let b = self.bounds;
+        let extent = glm::max2(&(b.max - b.min), &glm::vec3(1e-6, 1e-6, 1e-6));
+        let centroid = |t: &[u32; 3]| {
+            t.iter().map(|&i| self.positions[i as usize]).sum::<glm::Vec3>() / 3.0
+        };
+        let key = |t: &[u32; 3]| {
+            let q = (centroid(t) - b.min).component_div(&extent) * 1023.0;
+            morton([q.x as u32, q.y as u32, q.z as u32])
+        };
+
+        let mut indices = self.indices.clone();
+        let mut clusters = Vec::new();
+        for sub in &self.submeshes {
+            let tris = &mut indices[sub.start..sub.start + sub.count];
+            tris.sort_by_cached_key(key);
+            for (k, chunk) in tris.chunks(max_triangles).enumerate() {
+                let points: Vec<glm::Vec3> = chunk.iter().flatten().map(|&i| self.positions[i as usize]).collect();
+                clusters.push(Cluster {
+                    start: sub.start + k * max_triangles,
+                    count: chunk.len(),
+                    bounds: Bounds::from_points(points.iter()),
+                });
+            }
+        }
+        self.indices = indices;
+        self.clusters = clusters;
+    }
+}
@@ -0,0 +1,105 @@
+use nalgebra_glm as glm;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::mesh::Mesh;
+
+// Nombre seguro para archivos a partir del nombre del material
+fn file_safe(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+impl Mesh {
+    // Escribe la malla como OBJ con `transform` (y la de cada parte) horneada
+    // en posiciones y normales. Si hay materiales se escribe también un MTL
+    // junto al OBJ, con sus texturas como PNG.
+    pub fn save_obj(&self, path: &str, transform: &glm::Mat4) -> Result<(), String> {
+        let obj_path = Path::new(path);
+        let stem = obj_path.file_stem().and_then(|s| s.to_str()).unwrap_or("mesh");
+        let dir = obj_path.parent().unwrap_or(Path::new("."));
+
+        // Transformación por vértice según la parte a la que pertenece
+        let mut part_of = vec![usize::MAX; self.positions.len()];
+        for (p, sub) in self.submeshes.iter().enumerate() {
+            for t in &self.indices[sub.start..sub.start + sub.count] {
+                for &v in t {
+                    part_of[v as usize] = p;
+                }
+            }
+        }
+        let matrices: Vec<glm::Mat4> = self.submeshes.iter().map(|s| transform * s.transform).collect();
+        let matrix_of = |v: usize| matrices.get(part_of[v]).unwrap_or(transform);
+
+        let mut out = String::new();
+        out.push_str("# Exportado por modelo_nave_rs\n");
+        let has_mtl = !self.materials.is_empty();
+        if has_mtl {
+            let _ = writeln!(out, "mtllib {}.mtl", stem);
+        }
+
+        for (i, p) in self.positions.iter().enumerate() {
+            let q = (matrix_of(i) * glm::vec4(p.x, p.y, p.z, 1.0)).xyz();
+            match self.colors.get(i) {
+                Some(c) => { let _ = writeln!(out, "v {} {} {} {} {} {}", q.x, q.y, q.z, c.x, c.y, c.z); }
+                None => { let _ = writeln!(out, "v {} {} {}", q.x, q.y, q.z); }
+            }
+        }
+        for t in &self.uvs {
+            let _ = writeln!(out, "vt {} {}", t.x, t.y);
+        }
+        for (i, n) in self.normals.iter().enumerate() {
+            let m = glm::inverse_transpose(glm::mat4_to_mat3(matrix_of(i)));
+            let v = m * n;
+            let v = if v.magnitude() > 1e-12 { v.normalize() } else { v };
+            let _ = writeln!(out, "vn {} {} {}", v.x, v.y, v.z);
+        }
+
+        // Un índice compartido para v/vt/vn (base 1)
+        let has_uvs = !self.uvs.is_empty();
+        let corner = |v: u32| if has_uvs { format!("{0}/{0}/{0}", v + 1) } else { format!("{0}//{0}", v + 1) };
+        for sub in &self.submeshes {
+            let _ = writeln!(out, "o {}", sub.name);
+            if let Some(m) = sub.material.and_then(|i| self.materials.get(i)) {
+                let _ = writeln!(out, "usemtl {}", m.name);
+            }
+            // Un espejo en la transformación invierte el orden de los vértices
+            let mirrored = glm::mat4_to_mat3(&(transform * sub.transform)).determinant() < 0.0;
+            for t in &self.indices[sub.start..sub.start + sub.count] {
+                let t = if mirrored { [t[0], t[2], t[1]] } else { *t };
+                let _ = writeln!(out, "f {} {} {}", corner(t[0]), corner(t[1]), corner(t[2]));
+            }
+        }
+        std::fs::write(obj_path, out).map_err(|e| format!("Error guardando '{}': {}", path, e))?;
+
+        if has_mtl {
+            self.save_mtl(&dir.join(format!("{}.mtl", stem)), dir, stem)?;
+        }
+        Ok(())
+    }
+
+    fn save_mtl(&self, mtl_path: &Path, dir: &Path, stem: &str) -> Result<(), String> {
+        let mut out = String::new();
+        for m in &self.materials {
+            let _ = writeln!(out, "newmtl {}", m.name);
+            let _ = writeln!(out, "Kd {} {} {}", m.diffuse.x, m.diffuse.y, m.diffuse.z);
+            let _ = writeln!(out, "Ks {} {} {}", m.specular.x, m.specular.y, m.specular.z);
+            let _ = writeln!(out, "Ns {}", m.shininess);
+            let _ = writeln!(out, "d {}", m.opacity);
+            if let Some(pbr) = &m.pbr {
+                // Extensión PBR de MTL (la leen Blender y otros)
+                let _ = writeln!(out, "Pr {}", pbr.roughness);
+                let _ = writeln!(out, "Pm {}", pbr.metallic);
+                let _ = writeln!(out, "Ke {} {} {}", pbr.emissive.x, pbr.emissive.y, pbr.emissive.z);
+            }
+            for (tex, key, suffix) in [(&m.diffuse_map, "map_Kd", "kd"), (&m.normal_map, "map_Bump", "normal")] {
+                if let Some(tex) = tex {
+                    let file = format!("{}_{}_{}.png", stem, file_safe(&m.name), suffix);
+                    tex.save(&dir.join(&file))?;
+                    let _ = writeln!(out, "{} {}", key, file);
+                }
+            }
+            out.push('\n');
+        }
+        std::fs::write(mtl_path, out).map_err(|e| format!("Error guardando '{}': {}", mtl_path.display(), e))
+    }
+}
@@ -0,0 +1,255 @@
+use nalgebra_glm as glm;
+
+use crate::material::{Material, PbrParams};
+use crate::mesh::{LoadOptions, Mesh, SubMesh};
+use crate::texture::{Filter, Sampler, Texture, TextureFormat, Wrap};
+use gltf::texture::{MagFilter, MinFilter, WrappingMode};
+
+// Imagen decodificada por gltf (crate image) → textura R8, RGB8 o RGBA8.
+// Los formatos de 16 bits se reducen a su byte alto.
+fn texture_from_gltf(data: &gltf::image::Data) -> Result<Texture, String> {
+    use gltf::image::Format;
+    let (channels, step) = match data.format {
+        Format::R8 => (1, 1),
+        Format::R8G8 => (2, 1),
+        Format::R8G8B8 => (3, 1),
+        Format::R8G8B8A8 => (4, 1),
+        Format::R16 => (1, 2),
+        Format::R16G16 => (2, 2),
+        Format::R16G16B16 => (3, 2),
+        Format::R16G16B16A16 => (4, 2),
+        other => return Err(format!("formato de imagen no soportado: {:?}", other)),
+    };
+
+    // Dos canales (RG) se guardan como RGB con B = 0
+    let format = match channels {
+        1 => TextureFormat::R8,
+        2 | 3 => TextureFormat::Rgb8,
+        _ => TextureFormat::Rgba8,
+    };
+    let mut texels = Vec::with_capacity(data.pixels.len() / (channels * step) * format.channels());
+    for px in data.pixels.chunks_exact(channels * step) {
+        let c = |i: usize| px[i * step + step - 1];
+        match channels {
+            1 => texels.push(c(0)),
+            2 => texels.extend_from_slice(&[c(0), c(1), 0]),
+            3 => texels.extend_from_slice(&[c(0), c(1), c(2)]),
+            _ => texels.extend_from_slice(&[c(0), c(1), c(2), c(3)]),
+        }
+    }
+    Texture::from_u8(data.width as usize, data.height as usize, format, texels)
+}
+
+// Sin mipmaps en el minificado se queda en bilineal; un aumento "nearest"
+// (pixel art) manda sobre todo lo demás
+fn filter_from_gltf(mag: Option<MagFilter>, min: Option<MinFilter>) -> Filter {
+    match (mag, min) {
+        (Some(MagFilter::Nearest), _) => Filter::Nearest,
+        (_, Some(MinFilter::Nearest | MinFilter::Linear)) => Filter::Bilinear,
+        _ => Filter::Trilinear,
+    }
+}
+
+fn wrap_from_gltf(mode: WrappingMode) -> Wrap {
+    match mode {
+        WrappingMode::ClampToEdge => Wrap::Clamp,
+        WrappingMode::MirroredRepeat => Wrap::Mirror,
+        WrappingMode::Repeat => Wrap::Repeat,
+    }
+}
+
+fn material_from_gltf(m: &gltf::Material, images: &[gltf::image::Data]) -> Material {
+    let name = m.name().unwrap_or("gltf").to_string();
+    let texture = |tex: Option<gltf::texture::Texture>| {
+        let tex = tex?;
+        let data = images.get(tex.source().index())?;
+        let sampler = tex.sampler();
+        texture_from_gltf(data)
+            .map(|t| t.with_sampler(Sampler {
+                filter: filter_from_gltf(sampler.mag_filter(), sampler.min_filter()),
+                wrap_u: wrap_from_gltf(sampler.wrap_s()),
+                wrap_v: wrap_from_gltf(sampler.wrap_t()),
+                anisotropy: 1,
+            }))
+            .map_err(|e| eprintln!("Material '{}': {}", name, e))
+            .ok()
+    };
+
+    let pbr = m.pbr_metallic_roughness();
+    let base = pbr.base_color_factor();
+    let emissive = m.emissive_factor();
+    let blend = m.alpha_mode() == gltf::material::AlphaMode::Blend;
+
+    Material {
+        name: name.clone(),
+        diffuse: glm::vec3(base[0], base[1], base[2]),
+        specular: glm::vec3(0.04, 0.04, 0.04),
+        shininess: 2.0 / pbr.roughness_factor().max(0.05).powi(4) - 2.0,
+        opacity: if blend { base[3].clamp(0.0, 1.0) } else { 1.0 },
+        diffuse_map: texture(pbr.base_color_texture().map(|t| t.texture())),
+        normal_map: texture(m.normal_texture().map(|t| t.texture())),
+        pbr: Some(PbrParams {
+            metallic: pbr.metallic_factor().clamp(0.0, 1.0),
+            roughness: pbr.roughness_factor().clamp(0.04, 1.0),
+            emissive: glm::vec3(emissive[0], emissive[1], emissive[2]),
+            metallic_roughness_map: texture(pbr.metallic_roughness_texture().map(|t| t.texture())),
+        }),
+    }
+}
+
+// Geometría acumulada de todos los nodos, ya en el espacio de la escena
+#[derive(Default)]
+struct GltfGeometry {
+    positions: Vec<glm::Vec3>,
+    normals: Vec<glm::Vec3>,
+    uvs: Vec<glm::Vec2>,
+    tangents: Vec<glm::Vec4>,
+    indices: Vec<[u32; 3]>,
+    submeshes: Vec<SubMesh>,
+    all_normals: bool,
+    any_uvs: bool,
+    all_tangents: bool,
+    skipped: usize,
+}
+
+impl GltfGeometry {
+    // Recorre el nodo y sus hijos acumulando la transformación
+    fn visit(&mut self, node: &gltf::Node, parent: &glm::Mat4, buffers: &[gltf::buffer::Data]) -> Result<(), String> {
+        let local = glm::make_mat4(&node.transform().matrix().concat());
+        let world = parent * local;
+
+        if let Some(mesh) = node.mesh() {
+            let name = node.name().or(mesh.name()).unwrap_or("node");
+            for prim in mesh.primitives() {
+                if prim.mode() != gltf::mesh::Mode::Triangles {
+                    self.skipped += 1;
+                    continue;
+                }
+                self.add_primitive(name, &prim, &world, buffers)?;
+            }
+        }
+
+        for child in node.children() {
+            self.visit(&child, &world, buffers)?;
+        }
+        Ok(())
+    }
+
+    fn add_primitive(&mut self, name: &str, prim: &gltf::Primitive, world: &glm::Mat4, buffers: &[gltf::buffer::Data]) -> Result<(), String> {
+        let reader = prim.reader(|b| buffers.get(b.index()).map(|d| &d[..]));
+        let positions: Vec<[f32; 3]> = reader
+            .read_positions()
+            .ok_or_else(|| format!("primitiva sin posiciones en '{}'", name))?
+            .collect();
+        let normals: Option<Vec<[f32; 3]>> = reader.read_normals().map(|r| r.collect());
+        let uvs: Option<Vec<[f32; 2]>> = reader.read_tex_coords(0).map(|r| r.into_f32().collect());
+        let tangents: Option<Vec<[f32; 4]>> = reader.read_tangents().map(|r| r.collect());
+        let n = positions.len();
+        let normals = normals.filter(|v| v.len() == n);
+        let uvs = uvs.filter(|v| v.len() == n);
+        let tangents = tangents.filter(|v| v.len() == n);
+
+        let base = self.positions.len() as u32;
+        let tris: Vec<u32> = match reader.read_indices() {
+            Some(ix) => ix.into_u32().collect(),
+            None => (0..n as u32).collect(),
+        };
+        if tris.iter().any(|&i| i as usize >= n) {
+            return Err(format!("índice fuera de rango en '{}'", name));
+        }
+
+        // Normales con la inversa transpuesta; un espejo invierte el orden
+        let m3 = glm::mat4_to_mat3(world);
+        let normal_m = glm::inverse_transpose(m3);
+        let mirrored = m3.determinant() < 0.0;
+
+        for (i, p) in positions.iter().enumerate() {
+            self.positions.push((world * glm::vec4(p[0], p[1], p[2], 1.0)).xyz());
+            self.normals.push(match &normals {
+                Some(ns) => {
+                    let v = normal_m * glm::vec3(ns[i][0], ns[i][1], ns[i][2]);
+                    if v.magnitude() > 1e-12 { v.normalize() } else { v }
+                }
+                None => glm::Vec3::zeros(),
+            });
+            // glTF tiene el origen de las UVs arriba; aquí v crece hacia arriba como en OBJ
+            self.uvs.push(uvs.as_ref().map_or(glm::Vec2::zeros(), |t| glm::vec2(t[i][0], 1.0 - t[i][1])));
+            // Con v invertida la bitangente cambia de sentido (y otra vez si hay espejo)
+            self.tangents.push(tangents.as_ref().map_or(glm::Vec4::zeros(), |t| {
+                let xyz = m3 * glm::vec3(t[i][0], t[i][1], t[i][2]);
+                let xyz = if xyz.magnitude() > 1e-12 { xyz.normalize() } else { xyz };
+                let w = if mirrored { t[i][3] } else { -t[i][3] };
+                glm::vec4(xyz.x, xyz.y, xyz.z, w)
+            }));
+        }
+
+        let start = self.indices.len();
+        for c in tris.chunks_exact(3) {
+            self.indices.push(if mirrored {
+                [base + c[0], base + c[2], base + c[1]]
+            } else {
+                [base + c[0], base + c[1], base + c[2]]
+            });
+        }
+        if self.indices.len() > start {
+            self.submeshes.push(SubMesh::new(name, start, self.indices.len() - start, prim.material().index()));
+        }
+
+        self.all_normals &= normals.is_some();
+        self.any_uvs |= uvs.is_some();
+        self.all_tangents &= tangents.is_some() && uvs.is_some();
+        Ok(())
+    }
+}
+
+impl Mesh {
+    // glTF / GLB: nodos de la escena por defecto con sus transformaciones,
+    // materiales metálico-rugosos y texturas embebidas o externas.
+    pub fn load_gltf_with(path: &str, target_pixels: f32, opts: &LoadOptions) -> Result<Self, String> {
+        let (doc, buffers, images) = gltf::import(path).map_err(|e| format!("Error cargando '{}': {}", path, e))?;
+        let scene = doc
+            .default_scene()
+            .or_else(|| doc.scenes().next())
+            .ok_or_else(|| format!("'{}' no tiene escenas", path))?;
+
+        let mut geo = GltfGeometry { all_normals: true, all_tangents: true, ..Default::default() };
+        for node in scene.nodes() {
+            geo.visit(&node, &glm::identity(), &buffers)
+                .map_err(|e| format!("Error cargando '{}': {}", path, e))?;
+        }
+        if geo.skipped > 0 {
+            eprintln!("'{}': {} primitivas que no son triángulos omitidas", path, geo.skipped);
+        }
+        if geo.positions.is_empty() || geo.indices.is_empty() {
+            return Err(format!("glTF vacío o sin triángulos: '{}'", path));
+        }
+
+        let materials: Vec<Material> = doc.materials().map(|m| material_from_gltf(&m, &images)).collect();
+        for sub in &mut geo.submeshes {
+            sub.material = sub.material.filter(|&i| i < materials.len());
+        }
+
+        let uvs = if geo.any_uvs { geo.uvs } else { Vec::new() };
+        let mut mesh = Self::new(geo.positions, geo.normals, uvs, geo.indices, target_pixels);
+        mesh.submeshes = geo.submeshes;
+        mesh.materials = materials;
+        mesh.compute_bounds();
+
+        // Tangentes del archivo solo si valen para las normales conservadas
+        let keep_normals = geo.all_normals && opts.keep_file_normals;
+        if keep_normals && geo.all_tangents {
+            mesh.tangents = geo.tangents;
+        }
+        if let Some(weld) = &opts.weld {
+            mesh.weld_vertices(weld);
+        }
+        if !keep_normals {
+            mesh.compute_normals(opts.normals);
+        }
+        if mesh.tangents.is_empty() && !mesh.uvs.is_empty() {
+            mesh.compute_tangents().map_err(|e| format!("Error en tangentes de '{}': {}", path, e))?;
+        }
+
+        Ok(mesh)
+    }
+}
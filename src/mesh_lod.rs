@@ -0,0 +1,77 @@
+use crate::mesh::Mesh;
+
+// Un nivel se usa cuando el objeto mide menos de `max_radius_px` en pantalla
+// (y no tanto como para que le toque uno más grueso)
+#[derive(Clone)]
+pub struct LodLevel {
+    pub mesh: Mesh,
+    pub max_radius_px: f32,
+}
+
+// Varias versiones de la misma malla, de la más fina a la más gruesa. Todas
+// comparten centro y escala para que la proyección no cambie entre niveles.
+#[derive(Clone)]
+pub struct MeshLod {
+    pub levels: Vec<LodLevel>,
+    pub hysteresis: f32, // margen relativo alrededor de cada umbral
+    radius: f32,         // radio envolvente del nivel más fino
+    current: usize,
+}
+
+impl MeshLod {
+    // Nivel más fino, usado a cualquier tamaño hasta que se añadan otros
+    pub fn new(finest: Mesh) -> Self {
+        let radius = finest.radius();
+        Self {
+            levels: vec![LodLevel { mesh: finest, max_radius_px: f32::INFINITY }],
+            hysteresis: 0.1,
+            radius,
+            current: 0,
+        }
+    }
+
+    // Añade una versión más gruesa para radios por debajo de `max_radius_px`
+    pub fn push(&mut self, mut mesh: Mesh, max_radius_px: f32) {
+        mesh.center = self.levels[0].mesh.center;
+        mesh.scale = self.levels[0].mesh.scale;
+        let at = self.levels.iter().position(|l| l.max_radius_px < max_radius_px).unwrap_or(self.levels.len());
+        self.levels.insert(at, LodLevel { mesh, max_radius_px });
+        self.current = 0;
+    }
+
+    // Añade una copia simplificada del nivel más fino
+    pub fn push_simplified(&mut self, target_triangles: usize, max_radius_px: f32) {
+        let mut mesh = self.levels[0].mesh.clone();
+        mesh.simplify(target_triangles);
+        self.push(mesh, max_radius_px);
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn mesh(&self) -> &Mesh {
+        &self.levels[self.current].mesh
+    }
+
+    // Elige el nivel para un radio proyectado en píxeles. Para pasar a uno
+    // más fino hay que superar el umbral por el margen y para pasar a uno más
+    // grueso bajar de él por el mismo margen: cerca de un umbral no se
+    // alterna de nivel en cada cuadro. Devuelve true si cambió.
+    pub fn select(&mut self, radius_px: f32) -> bool {
+        let mut i = self.current;
+        while i > 0 && radius_px >= self.levels[i].max_radius_px * (1.0 + self.hysteresis) {
+            i -= 1;
+        }
+        while i + 1 < self.levels.len() && radius_px < self.levels[i + 1].max_radius_px * (1.0 - self.hysteresis) {
+            i += 1;
+        }
+        let changed = i != self.current;
+        self.current = i;
+        changed
+    }
+}
@@ -0,0 +1,115 @@
+use nalgebra_glm as glm;
+use std::collections::HashMap;
+
+use crate::mesh::Mesh;
+
+// Cómo generar normales cuando el archivo no las trae (o se piden nuevas)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NormalMode {
+    Flat,            // Una normal por cara (vértices partidos en cada triángulo)
+    Smooth,          // Promedio de todas las caras que comparten la posición
+    Creased(f32),    // Suave salvo en aristas con ángulo mayor (radianes)
+}
+
+impl NormalMode {
+    pub fn creased_degrees(deg: f32) -> Self {
+        NormalMode::Creased(deg.to_radians())
+    }
+}
+
+// Ángulo interior del triángulo en la esquina `a`
+fn corner_angle(a: glm::Vec3, b: glm::Vec3, c: glm::Vec3) -> f32 {
+    let e1 = b - a;
+    let e2 = c - a;
+    let denom = e1.magnitude() * e2.magnitude();
+    if denom < 1e-20 { return 0.0; }
+    (e1.dot(&e2) / denom).clamp(-1.0, 1.0).acos()
+}
+
+#[inline]
+fn key_vec3(v: glm::Vec3) -> [u32; 3] {
+    [v.x.to_bits(), v.y.to_bits(), v.z.to_bits()]
+}
+
+impl Mesh {
+    // Recalcula las normales. Las caras se agrupan por posición (no por índice
+    // de vértice) para suavizar a través de costuras de UV; cada esquina suma
+    // las normales de las caras vecinas ponderadas por su ángulo interior, y
+    // solo las que están dentro del ángulo de pliegue. Los vértices cuyas
+    // esquinas terminan con normales distintas se parten.
+    pub fn compute_normals(&mut self, mode: NormalMode) {
+        let cos_crease = match mode {
+            NormalMode::Flat => 1.0 + 1e-6,    // ninguna vecina se acepta salvo la propia
+            NormalMode::Smooth => -1.0 - 1e-6, // todas
+            NormalMode::Creased(a) => a.cos(),
+        };
+
+        // Normal unitaria y ángulos por cara
+        let mut face_n = Vec::with_capacity(self.indices.len());
+        let mut face_w = Vec::with_capacity(self.indices.len());
+        for t in &self.indices {
+            let (a, b, c) = (self.positions[t[0] as usize], self.positions[t[1] as usize], self.positions[t[2] as usize]);
+            let n = (b - a).cross(&(c - a));
+            face_n.push(if n.magnitude() > 1e-20 { n.normalize() } else { glm::Vec3::zeros() });
+            face_w.push([corner_angle(a, b, c), corner_angle(b, c, a), corner_angle(c, a, b)]);
+        }
+
+        // Caras incidentes por posición: (cara, esquina)
+        let mut pos_group: HashMap<[u32; 3], u32> = HashMap::new();
+        let mut vert_group = Vec::with_capacity(self.positions.len());
+        for p in &self.positions {
+            let next = pos_group.len() as u32;
+            vert_group.push(*pos_group.entry(key_vec3(*p)).or_insert(next));
+        }
+        let mut incident: Vec<Vec<(u32, u8)>> = vec![Vec::new(); pos_group.len()];
+        for (f, t) in self.indices.iter().enumerate() {
+            for (k, &v) in t.iter().enumerate() {
+                incident[vert_group[v as usize] as usize].push((f as u32, k as u8));
+            }
+        }
+
+        // Normal por esquina y partición de vértices según (vértice, normal)
+        let mut split: HashMap<(u32, [u32; 3]), u32> = HashMap::new();
+        let mut sources: Vec<u32> = Vec::new();
+        let mut new_normals: Vec<glm::Vec3> = Vec::new();
+        let mut new_indices = Vec::with_capacity(self.indices.len());
+
+        for (f, t) in self.indices.iter().enumerate() {
+            let nf = face_n[f];
+            let mut tri = [0u32; 3];
+            for (k, &v) in t.iter().enumerate() {
+                let mut acc = glm::Vec3::zeros();
+                for &(g, gk) in &incident[vert_group[v as usize] as usize] {
+                    let ng = face_n[g as usize];
+                    if g as usize == f || nf.dot(&ng) >= cos_crease {
+                        acc += ng * face_w[g as usize][gk as usize];
+                    }
+                }
+                let n = if acc.magnitude() > 1e-20 {
+                    acc.normalize()
+                } else if nf.magnitude() > 0.0 {
+                    nf
+                } else {
+                    glm::vec3(0.0, 1.0, 0.0)
+                };
+
+                tri[k] = *split.entry((v, key_vec3(n))).or_insert_with(|| {
+                    sources.push(v);
+                    new_normals.push(n);
+                    sources.len() as u32 - 1
+                });
+            }
+            new_indices.push(tri);
+        }
+
+        self.reindex_vertices(&sources);
+        self.normals = new_normals;
+        self.indices = new_indices;
+
+        // Las tangentes dependen de las normales: rehacerlas si existían
+        // (si fallan, compute_tangents las deja vacías)
+        if !self.tangents.is_empty() {
+            self.compute_tangents().ok();
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use nalgebra_glm as glm;
+
+use crate::mesh::{LoadOptions, Mesh};
+
+// Tipos escalares de PLY (nombres clásicos y con tamaño)
+#[derive(Clone, Copy, PartialEq)]
+enum Scalar { I8, U8, I16, U16, I32, U32, F32, F64 }
+
+impl Scalar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => Scalar::I8,
+            "uchar" | "uint8" => Scalar::U8,
+            "short" | "int16" => Scalar::I16,
+            "ushort" | "uint16" => Scalar::U16,
+            "int" | "int32" => Scalar::I32,
+            "uint" | "uint32" => Scalar::U32,
+            "float" | "float32" => Scalar::F32,
+            "double" | "float64" => Scalar::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+
+    // Escala para llevar un color entero a [0,1]
+    fn color_scale(self) -> f64 {
+        match self {
+            Scalar::U8 | Scalar::I8 => 1.0 / 255.0,
+            Scalar::U16 | Scalar::I16 => 1.0 / 65535.0,
+            _ => 1.0,
+        }
+    }
+}
+
+enum Property {
+    Scalar { name: String, ty: Scalar },
+    List { name: String, count: Scalar, item: Scalar },
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    props: Vec<Property>,
+}
+
+// Lector de valores del cuerpo: palabras de texto o bytes
+enum Body<'a> {
+    Ascii(std::str::SplitWhitespace<'a>),
+    Binary { data: &'a [u8], pos: usize, big_endian: bool },
+}
+
+impl Body<'_> {
+    fn read(&mut self, ty: Scalar) -> Result<f64, String> {
+        match self {
+            Body::Ascii(words) => {
+                let w = words.next().ok_or("datos PLY incompletos")?;
+                w.parse::<f64>().map_err(|_| format!("valor PLY inválido: '{}'", w))
+            }
+            Body::Binary { data, pos, big_endian } => {
+                let n = ty.size();
+                let bytes = data.get(*pos..*pos + n).ok_or("datos PLY incompletos")?;
+                *pos += n;
+                let mut buf = [0u8; 8];
+                buf[..n].copy_from_slice(bytes);
+                if *big_endian {
+                    buf[..n].reverse();
+                }
+                Ok(match ty {
+                    Scalar::I8 => buf[0] as i8 as f64,
+                    Scalar::U8 => buf[0] as f64,
+                    Scalar::I16 => i16::from_le_bytes([buf[0], buf[1]]) as f64,
+                    Scalar::U16 => u16::from_le_bytes([buf[0], buf[1]]) as f64,
+                    Scalar::I32 => i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+                    Scalar::U32 => u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+                    Scalar::F32 => f32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+                    Scalar::F64 => f64::from_le_bytes(buf),
+                })
+            }
+        }
+    }
+}
+
+// Índice de vértice de una cara: entero, no negativo y finito
+fn vertex_index(v: f64) -> Result<u32, String> {
+    if v.is_finite() && v >= 0.0 && v.fract() == 0.0 && v <= u32::MAX as f64 {
+        Ok(v as u32)
+    } else {
+        Err(format!("índice de cara PLY inválido: {}", v))
+    }
+}
+
+// Longitud de una lista: entera, no negativa y finita
+fn list_len(v: f64) -> Result<usize, String> {
+    if v.is_finite() && v >= 0.0 && v.fract() == 0.0 && v <= u32::MAX as f64 {
+        Ok(v as usize)
+    } else {
+        Err(format!("longitud de lista PLY inválida: {}", v))
+    }
+}
+
+struct PlyData {
+    positions: Vec<glm::Vec3>,
+    normals: Option<Vec<glm::Vec3>>,
+    uvs: Option<Vec<glm::Vec2>>,
+    colors: Option<Vec<glm::Vec3>>,
+    indices: Vec<[u32; 3]>,
+}
+
+fn parse_header(text: &str) -> Result<(String, Vec<Element>), String> {
+    let mut lines = text.lines();
+    if lines.next().map(str::trim) != Some("ply") {
+        return Err("no es un archivo PLY".into());
+    }
+    let mut format = String::new();
+    let mut elements: Vec<Element> = Vec::new();
+    for line in lines {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", f, ..] => format = f.to_string(),
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| format!("cantidad inválida: '{}'", line))?,
+                props: Vec::new(),
+            }),
+            ["property", "list", count, item, name] => {
+                let (count, item) = Scalar::parse(count).zip(Scalar::parse(item))
+                    .ok_or_else(|| format!("tipo desconocido: '{}'", line))?;
+                elements.last_mut().ok_or("propiedad fuera de un elemento")?
+                    .props.push(Property::List { name: name.to_string(), count, item });
+            }
+            ["property", ty, name] => {
+                let ty = Scalar::parse(ty).ok_or_else(|| format!("tipo desconocido: '{}'", line))?;
+                elements.last_mut().ok_or("propiedad fuera de un elemento")?
+                    .props.push(Property::Scalar { name: name.to_string(), ty });
+            }
+            _ => {} // comment, obj_info, end_header
+        }
+    }
+    Ok((format, elements))
+}
+
+fn parse_ply(bytes: &[u8]) -> Result<PlyData, String> {
+    let marker = b"end_header";
+    let end = bytes.windows(marker.len()).position(|w| w == marker).ok_or("cabecera PLY sin end_header")?;
+    // El cuerpo empieza tras el salto de línea (\n o \r\n)
+    let mut body_start = end + marker.len();
+    while body_start < bytes.len() && (bytes[body_start] == b'\r' || bytes[body_start] == b' ') { body_start += 1; }
+    body_start += 1;
+    let header = std::str::from_utf8(&bytes[..end]).map_err(|_| "cabecera PLY no válida")?;
+    let (format, elements) = parse_header(header)?;
+
+    let data = bytes.get(body_start..).unwrap_or(&[]);
+    let mut body = match format.as_str() {
+        "ascii" => Body::Ascii(std::str::from_utf8(data).map_err(|_| "cuerpo PLY ASCII no válido")?.split_whitespace()),
+        "binary_little_endian" => Body::Binary { data, pos: 0, big_endian: false },
+        "binary_big_endian" => Body::Binary { data, pos: 0, big_endian: true },
+        other => return Err(format!("formato PLY no soportado: '{}'", other)),
+    };
+
+    // Cada valor ocupa al menos un byte del cuerpo: las cantidades de la
+    // cabecera o de las listas no pueden reservar más que eso
+    let max_values = data.len();
+    let mut out = PlyData { positions: Vec::new(), normals: None, uvs: None, colors: None, indices: Vec::new() };
+    for el in &elements {
+        if el.props.is_empty() { continue; }
+        let capacity = el.count.min(max_values);
+        // Índice de cada propiedad escalar conocida dentro de la fila
+        let find = |names: &[&str]| el.props.iter().position(|p| matches!(p, Property::Scalar { name, .. } if names.contains(&name.as_str())));
+        let is_vertex = el.name == "vertex";
+        let pos = [find(&["x"]), find(&["y"]), find(&["z"])];
+        let nrm = [find(&["nx"]), find(&["ny"]), find(&["nz"])];
+        let uv = [find(&["u", "s", "texture_u", "texture_s"]), find(&["v", "t", "texture_v", "texture_t"])];
+        let col = [find(&["red", "diffuse_red"]), find(&["green", "diffuse_green"]), find(&["blue", "diffuse_blue"])];
+        let has_nrm = nrm.iter().all(Option::is_some);
+        let has_uv = uv.iter().all(Option::is_some);
+        let has_col = col.iter().all(Option::is_some);
+        if is_vertex {
+            if pos.iter().any(Option::is_none) {
+                return Err("los vértices PLY no tienen x/y/z".into());
+            }
+            out.positions.reserve(capacity);
+            if has_nrm { out.normals = Some(Vec::with_capacity(capacity)); }
+            if has_uv { out.uvs = Some(Vec::with_capacity(capacity)); }
+            if has_col { out.colors = Some(Vec::with_capacity(capacity)); }
+        }
+
+        let mut row = vec![0.0f64; el.props.len()];
+        let mut scales = vec![1.0f64; el.props.len()];
+        for (k, p) in el.props.iter().enumerate() {
+            if let Property::Scalar { ty, .. } = p { scales[k] = ty.color_scale(); }
+        }
+        for _ in 0..el.count {
+            for (k, p) in el.props.iter().enumerate() {
+                match p {
+                    Property::Scalar { ty, .. } => row[k] = body.read(*ty)?,
+                    Property::List { name, count, item } => {
+                        let n = list_len(body.read(*count)?)?;
+                        let mut items = Vec::with_capacity(n.min(max_values));
+                        for _ in 0..n { items.push(body.read(*item)?); }
+                        if el.name == "face" && (name == "vertex_indices" || name == "vertex_index") {
+                            let items = items.into_iter().map(vertex_index).collect::<Result<Vec<u32>, String>>()?;
+                            // Polígonos en abanico
+                            for j in 1..n.saturating_sub(1) {
+                                out.indices.push([items[0], items[j], items[j + 1]]);
+                            }
+                        }
+                    }
+                }
+            }
+            if is_vertex {
+                let g = |i: Option<usize>| row[i.unwrap_or(0)] as f32;
+                out.positions.push(glm::vec3(g(pos[0]), g(pos[1]), g(pos[2])));
+                if let Some(ns) = &mut out.normals { ns.push(glm::vec3(g(nrm[0]), g(nrm[1]), g(nrm[2]))); }
+                if let Some(ts) = &mut out.uvs { ts.push(glm::vec2(g(uv[0]), g(uv[1]))); }
+                if let Some(cs) = &mut out.colors {
+                    let c = |i: Option<usize>| (row[i.unwrap_or(0)] * scales[i.unwrap_or(0)]) as f32;
+                    cs.push(glm::vec3(c(col[0]), c(col[1]), c(col[2])));
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+impl Mesh {
+    // PLY ASCII o binario: posiciones, normales, UVs y colores por vértice;
+    // caras poligonales trianguladas en abanico.
+    pub fn load_ply_with(path: &str, target_pixels: f32, opts: &LoadOptions) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Error cargando '{}': {}", path, e))?;
+        let ply = parse_ply(&bytes).map_err(|e| format!("Error cargando '{}': {}", path, e))?;
+
+        let n = ply.positions.len() as u32;
+        let out_of_range = ply.indices.iter().filter(|t| t.iter().any(|&i| i >= n)).count();
+        if out_of_range > 0 {
+            return Err(format!("Índices fuera de rango en {}: {} de {} triángulos", path, out_of_range, ply.indices.len()));
+        }
+        if ply.positions.is_empty() || ply.indices.is_empty() {
+            return Err(format!("PLY vacío o sin caras: '{}'", path));
+        }
+
+        let has_normals = ply.normals.is_some();
+        let normals = ply.normals.unwrap_or_else(|| vec![glm::Vec3::zeros(); n as usize])
+            .into_iter()
+            .map(|v| if v.magnitude() > 1e-12 { v.normalize() } else { v })
+            .collect();
+        let mut mesh = Self::new(ply.positions, normals, ply.uvs.unwrap_or_default(), ply.indices, target_pixels);
+        mesh.colors = ply.colors.unwrap_or_default();
+        if let Some(weld) = &opts.weld {
+            mesh.weld_vertices(weld);
+        }
+
+        if !has_normals || !opts.keep_file_normals {
+            mesh.compute_normals(opts.normals);
+        }
+        if !mesh.uvs.is_empty() {
+            mesh.compute_tangents().map_err(|e| format!("Error en tangentes de '{}': {}", path, e))?;
+        }
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "element vertex 4\nproperty float x\nproperty float y\nproperty float z\n\
+        property uchar red\nproperty uchar green\nproperty uchar blue\n\
+        element face 1\nproperty list uchar int vertex_indices\nend_header\n";
+
+    fn ply(format: &str) -> Vec<u8> {
+        format!("ply\nformat {} 1.0\n{}", format, HEADER).into_bytes()
+    }
+
+    // Un cuadrado como polígono de 4 vértices, con el último vértice rojo
+    fn binary(big_endian: bool) -> Vec<u8> {
+        let mut bytes = ply(if big_endian { "binary_big_endian" } else { "binary_little_endian" });
+        let f = |v: f32| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+        for (i, (x, y)) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].into_iter().enumerate() {
+            bytes.extend(f(x));
+            bytes.extend(f(y));
+            bytes.extend(f(0.0));
+            bytes.extend(if i == 3 { [255, 0, 0] } else { [255, 255, 255] });
+        }
+        bytes.push(4);
+        for i in [0i32, 1, 2, 3] {
+            bytes.extend(if big_endian { i.to_be_bytes() } else { i.to_le_bytes() });
+        }
+        bytes
+    }
+
+    fn check_square(data: &PlyData) {
+        assert_eq!(data.positions.len(), 4);
+        assert_eq!(data.positions[2], glm::vec3(1.0, 1.0, 0.0));
+        assert_eq!(data.indices, vec![[0, 1, 2], [0, 2, 3]]);
+        assert_eq!(data.colors.as_ref().map(|c| c[3]), Some(glm::vec3(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn ascii() {
+        let mut bytes = ply("ascii");
+        bytes.extend(b"0 0 0 255 255 255\n1 0 0 255 255 255\n1 1 0 255 255 255\n0 1 0 255 0 0\n4 0 1 2 3\n");
+        check_square(&parse_ply(&bytes).unwrap());
+    }
+
+    #[test]
+    fn binary_little_endian() {
+        check_square(&parse_ply(&binary(false)).unwrap());
+    }
+
+    #[test]
+    fn binary_big_endian() {
+        check_square(&parse_ply(&binary(true)).unwrap());
+    }
+
+    #[test]
+    fn rejects_invalid_face_indices() {
+        for face in ["3 -1 1 2", "3 0 1.7 2", "3 0 1 nan", "3 0 1 inf"] {
+            let mut bytes = ply("ascii");
+            bytes.extend(b"0 0 0 0 0 0\n1 0 0 0 0 0\n1 1 0 0 0 0\n0 1 0 0 0 0\n");
+            bytes.extend(face.as_bytes());
+            assert!(parse_ply(&bytes).is_err(), "cara aceptada: {}", face);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_counts() {
+        // Listas con longitud negativa, fraccionaria o no finita
+        for face in ["-1 0 1 2", "2.5 0 1 2", "nan 0 1 2"] {
+            let mut bytes = ply("ascii");
+            bytes.extend(b"0 0 0 0 0 0\n1 0 0 0 0 0\n1 1 0 0 0 0\n0 1 0 0 0 0\n");
+            bytes.extend(face.as_bytes());
+            assert!(parse_ply(&bytes).is_err(), "lista aceptada: {}", face);
+        }
+
+        // Longitud enorme en binario: error, no una reserva imposible
+        let mut bytes = b"ply\nformat binary_little_endian 1.0\nelement vertex 3\nproperty float x\n\
+            property float y\nproperty float z\nelement face 1\nproperty list uint int vertex_indices\nend_header\n".to_vec();
+        bytes.extend([0u8; 36]);
+        bytes.extend(0xf0ff_ffffu32.to_le_bytes());
+        assert!(parse_ply(&bytes).is_err());
+
+        // Cantidad de vértices de la cabecera mayor que el cuerpo
+        let mut bytes = b"ply\nformat ascii 1.0\nelement vertex 999999999999999\nproperty float x\nproperty float y\n\
+            property float z\nproperty float nx\nproperty float ny\nproperty float nz\nend_header\n".to_vec();
+        bytes.extend(b"0 0 0 0 0 1\n");
+        assert!(parse_ply(&bytes).is_err());
+    }
+}
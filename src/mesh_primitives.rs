@@ -0,0 +1,243 @@
+use nalgebra_glm as glm;
+use std::collections::HashMap;
+
+use crate::mesh::Mesh;
+
+// Anillo plano (corona circular) en el plano XZ alrededor de `center`, normal +Y.
+// `segments` divisiones angulares y `rings` divisiones radiales.
+pub fn ring(center: glm::Vec3, inner: f32, outer: f32, segments: usize, rings: usize) -> Mesh {
+    let segments = segments.max(3);
+    let rings = rings.max(1);
+
+    let mut positions = Vec::with_capacity(segments * (rings + 1));
+    let mut normals   = Vec::with_capacity(segments * (rings + 1));
+    let mut uvs       = Vec::with_capacity(segments * (rings + 1));
+    let mut indices   = Vec::with_capacity(segments * rings * 2);
+
+    for s in 0..segments {
+        let a = s as f32 / segments as f32 * std::f32::consts::TAU;
+        let (sin_a, cos_a) = a.sin_cos();
+        for k in 0..=rings {
+            let r = inner + (outer - inner) * (k as f32 / rings as f32);
+            positions.push(center + glm::vec3(cos_a * r, 0.0, sin_a * r));
+            normals.push(glm::vec3(0.0, 1.0, 0.0));
+            uvs.push(glm::vec2(k as f32 / rings as f32, s as f32 / segments as f32)); // u radial, v angular
+        }
+    }
+
+    let stride = (rings + 1) as u32;
+    for s in 0..segments as u32 {
+        let next = (s + 1) % segments as u32;
+        for k in 0..rings as u32 {
+            let a = s * stride + k;
+            let b = next * stride + k;
+            indices.push([a, b, a + 1]);
+            indices.push([a + 1, b, b + 1]);
+        }
+    }
+
+    Mesh::new(positions, normals, uvs, indices, 1.0)
+}
+
+// Malla de una sola parte con tangentes (todas las primitivas traen UVs)
+fn finish(positions: Vec<glm::Vec3>, normals: Vec<glm::Vec3>, uvs: Vec<glm::Vec2>, indices: Vec<[u32; 3]>) -> Mesh {
+    let mut mesh = Mesh::new(positions, normals, uvs, indices, 1.0);
+    if mesh.uvs.is_empty() {
+        mesh.compute_spherical_uvs();
+    }
+    mesh.compute_tangents().ok();
+    mesh
+}
+
+// Esfera por latitud/longitud centrada en el origen. Las UVs siguen la
+// proyección equirectangular de `compute_spherical_uvs` (columna de costura
+// duplicada; en los polos cada columna tiene su propio vértice).
+pub fn uv_sphere(radius: f32, segments: usize, rings: usize) -> Mesh {
+    let segments = segments.max(3);
+    let rings = rings.max(2);
+    let cols = segments + 1;
+
+    let mut positions = Vec::with_capacity(cols * (rings + 1));
+    let mut normals   = Vec::with_capacity(cols * (rings + 1));
+    let mut uvs       = Vec::with_capacity(cols * (rings + 1));
+    let mut indices   = Vec::with_capacity(segments * rings * 2);
+
+    for i in 0..=rings {
+        let phi = i as f32 / rings as f32 * std::f32::consts::PI; // desde el polo norte
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        for j in 0..cols {
+            // En los polos la u va al centro de la columna para no torcer la textura
+            let u = if i == 0 || i == rings { (j as f32 + 0.5) / segments as f32 } else { j as f32 / segments as f32 };
+            let theta = (u - 0.5) * std::f32::consts::TAU;
+            let n = glm::vec3(theta.sin() * sin_phi, cos_phi, -theta.cos() * sin_phi);
+            positions.push(n * radius);
+            normals.push(n);
+            uvs.push(glm::vec2(u, 1.0 - i as f32 / rings as f32));
+        }
+    }
+
+    let cols = cols as u32;
+    for i in 0..rings as u32 {
+        for j in 0..segments as u32 {
+            let a = i * cols + j;
+            let b = a + cols;
+            if i + 1 < rings as u32 { indices.push([a, b + 1, b]); }
+            if i > 0 { indices.push([a, a + 1, b + 1]); }
+        }
+    }
+
+    finish(positions, normals, uvs, indices)
+}
+
+// Icosaedro subdividido: triángulos casi iguales en toda la esfera.
+// Cada subdivisión multiplica por 4 los triángulos (20·4^n).
+pub fn icosphere(radius: f32, subdivisions: usize) -> Mesh {
+    let t = (1.0 + 5.0f32.sqrt()) * 0.5;
+    let mut dirs: Vec<glm::Vec3> = [
+        (-1.0, t, 0.0), (1.0, t, 0.0), (-1.0, -t, 0.0), (1.0, -t, 0.0),
+        (0.0, -1.0, t), (0.0, 1.0, t), (0.0, -1.0, -t), (0.0, 1.0, -t),
+        (t, 0.0, -1.0), (t, 0.0, 1.0), (-t, 0.0, -1.0), (-t, 0.0, 1.0),
+    ]
+    .iter()
+    .map(|&(x, y, z)| glm::vec3(x, y, z).normalize())
+    .collect();
+    let mut indices: Vec<[u32; 3]> = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        // Punto medio de cada arista, compartido por sus dos triángulos
+        let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut midpoint = |a: u32, b: u32, dirs: &mut Vec<glm::Vec3>| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                dirs.push((dirs[a as usize] + dirs[b as usize]).normalize());
+                dirs.len() as u32 - 1
+            })
+        };
+        let mut next = Vec::with_capacity(indices.len() * 4);
+        for &[a, b, c] in &indices {
+            let ab = midpoint(a, b, &mut dirs);
+            let bc = midpoint(b, c, &mut dirs);
+            let ca = midpoint(c, a, &mut dirs);
+            next.extend_from_slice(&[[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]);
+        }
+        indices = next;
+    }
+
+    let positions = dirs.iter().map(|d| d * radius).collect();
+    finish(positions, dirs, Vec::new(), indices)
+}
+
+// Cubo de `resolution`×`resolution` cuadros por cara proyectado a la esfera
+// (mapeo que reparte el área mejor que normalizar). UVs equirectangulares.
+pub fn cube_sphere(radius: f32, resolution: usize) -> Mesh {
+    let res = resolution.max(1);
+    // (normal, eje u, eje v) con u × v = normal: caras hacia fuera
+    let x = glm::vec3(1.0, 0.0, 0.0);
+    let y = glm::vec3(0.0, 1.0, 0.0);
+    let z = glm::vec3(0.0, 0.0, 1.0);
+    let faces = [(x, -z, y), (-x, z, y), (y, x, -z), (-y, x, z), (z, x, y), (-z, -x, y)];
+
+    let mut dirs = Vec::with_capacity(6 * (res + 1) * (res + 1));
+    let mut indices = Vec::with_capacity(6 * res * res * 2);
+    for (n, u_axis, v_axis) in faces {
+        let base = dirs.len() as u32;
+        for j in 0..=res {
+            for i in 0..=res {
+                let p = n + u_axis * (2.0 * i as f32 / res as f32 - 1.0) + v_axis * (2.0 * j as f32 / res as f32 - 1.0);
+                let (x2, y2, z2) = (p.x * p.x, p.y * p.y, p.z * p.z);
+                dirs.push(glm::vec3(
+                    p.x * (1.0 - y2 / 2.0 - z2 / 2.0 + y2 * z2 / 3.0).sqrt(),
+                    p.y * (1.0 - z2 / 2.0 - x2 / 2.0 + z2 * x2 / 3.0).sqrt(),
+                    p.z * (1.0 - x2 / 2.0 - y2 / 2.0 + x2 * y2 / 3.0).sqrt(),
+                ).normalize());
+            }
+        }
+        let stride = (res + 1) as u32;
+        for j in 0..res as u32 {
+            for i in 0..res as u32 {
+                let a = base + j * stride + i;
+                let d = a + stride;
+                indices.push([a, a + 1, d + 1]);
+                indices.push([a, d + 1, d]);
+            }
+        }
+    }
+
+    let positions = dirs.iter().map(|d| d * radius).collect();
+    finish(positions, dirs, Vec::new(), indices)
+}
+
+// Toro en el plano XZ alrededor de +Y. u recorre el anillo mayor y v el tubo.
+pub fn torus(major: f32, minor: f32, segments: usize, sides: usize) -> Mesh {
+    let segments = segments.max(3);
+    let sides = sides.max(3);
+    let cols = sides + 1;
+
+    let mut positions = Vec::with_capacity((segments + 1) * cols);
+    let mut normals   = Vec::with_capacity((segments + 1) * cols);
+    let mut uvs       = Vec::with_capacity((segments + 1) * cols);
+    let mut indices   = Vec::with_capacity(segments * sides * 2);
+
+    for i in 0..=segments {
+        let u = i as f32 / segments as f32;
+        let (sin_a, cos_a) = (u * std::f32::consts::TAU).sin_cos();
+        for j in 0..cols {
+            let v = j as f32 / sides as f32;
+            let (sin_b, cos_b) = (v * std::f32::consts::TAU).sin_cos();
+            let n = glm::vec3(cos_b * cos_a, sin_b, cos_b * sin_a);
+            positions.push(glm::vec3(cos_a * major, 0.0, sin_a * major) + n * minor);
+            normals.push(n);
+            uvs.push(glm::vec2(u, v));
+        }
+    }
+
+    let cols = cols as u32;
+    for i in 0..segments as u32 {
+        for j in 0..sides as u32 {
+            let a = i * cols + j;
+            let b = a + cols;
+            indices.push([a, b + 1, b]);
+            indices.push([a, a + 1, b + 1]);
+        }
+    }
+
+    finish(positions, normals, uvs, indices)
+}
+
+// Disco en el plano XZ con normal +Y: vértice central y `rings` coronas.
+// UVs planas (vista desde arriba, -Z hacia arriba de la imagen).
+pub fn disc(radius: f32, segments: usize, rings: usize) -> Mesh {
+    let segments = segments.max(3);
+    let rings = rings.max(1);
+
+    let mut positions = vec![glm::Vec3::zeros()];
+    let mut uvs = vec![glm::vec2(0.5, 0.5)];
+    for k in 1..=rings {
+        let r = radius * k as f32 / rings as f32;
+        for s in 0..segments {
+            let (sin_a, cos_a) = (s as f32 / segments as f32 * std::f32::consts::TAU).sin_cos();
+            positions.push(glm::vec3(cos_a * r, 0.0, sin_a * r));
+            uvs.push(glm::vec2(0.5 + cos_a * r / (2.0 * radius), 0.5 - sin_a * r / (2.0 * radius)));
+        }
+    }
+    let normals = vec![glm::vec3(0.0, 1.0, 0.0); positions.len()];
+
+    let seg = segments as u32;
+    let ring_start = |k: u32| 1 + (k - 1) * seg;
+    let mut indices = Vec::with_capacity(segments * (2 * rings - 1));
+    for s in 0..seg {
+        let next = (s + 1) % seg;
+        indices.push([0, ring_start(1) + next, ring_start(1) + s]);
+        for k in 1..rings as u32 {
+            let (a, b) = (ring_start(k), ring_start(k + 1));
+            indices.push([a + s, a + next, b + next]);
+            indices.push([a + s, b + next, b + s]);
+        }
+    }
+
+    finish(positions, normals, uvs, indices)
+}
@@ -0,0 +1,410 @@
+use nalgebra_glm as glm;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+
+use crate::mesh::Mesh;
+
+// Peso de los planos que fijan bordes abiertos y costuras de UV/normales
+const BORDER_WEIGHT: f64 = 1000.0;
+
+// Cuádrica simétrica 4x4 (Garland–Heckbert) guardada como triángulo superior
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    // Distancia al cuadrado al plano n·p + d = 0, con peso
+    fn plane(n: glm::DVec3, d: f64, w: f64) -> Self {
+        let (a, b, c) = (n.x, n.y, n.z);
+        Self([a * a * w, a * b * w, a * c * w, a * d * w, b * b * w, b * c * w, b * d * w, c * c * w, c * d * w, d * d * w])
+    }
+
+    fn add(&mut self, o: &Quadric) {
+        for (a, b) in self.0.iter_mut().zip(o.0.iter()) {
+            *a += b;
+        }
+    }
+
+    fn error(&self, p: glm::DVec3) -> f64 {
+        let q = &self.0;
+        let (x, y, z) = (p.x, p.y, p.z);
+        q[0] * x * x + 2.0 * q[1] * x * y + 2.0 * q[2] * x * z + 2.0 * q[3] * x
+            + q[4] * y * y + 2.0 * q[5] * y * z + 2.0 * q[6] * y
+            + q[7] * z * z + 2.0 * q[8] * z
+            + q[9]
+    }
+}
+
+// Colapso candidato u → v (u desaparece y sus esquinas pasan a v)
+struct Collapse {
+    cost: f64,
+    u: u32,
+    v: u32,
+    stamps: (u32, u32),
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, o: &Self) -> bool { self.cost == o.cost }
+}
+impl Eq for Collapse {}
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, o: &Self) -> Option<Ordering> { Some(self.cmp(o)) }
+}
+impl Ord for Collapse {
+    // Montículo de mínimos por coste
+    fn cmp(&self, o: &Self) -> Ordering { o.cost.total_cmp(&self.cost) }
+}
+
+// Estado de la simplificación. Los vértices de la malla ("esquinas", con su
+// UV y normal) se agrupan por posición; los colapsos se hacen entre
+// posiciones y cada esquina de u se reasigna a la esquina de v del mismo lado
+// de la costura. Sin esa correspondencia el colapso se descarta, así las
+// costuras de UV y los pliegues de normales se conservan.
+struct Simplifier {
+    points: Vec<glm::DVec3>,
+    corner_pos: Vec<u32>,       // esquina → posición
+    faces: Vec<[u32; 3]>,       // esquinas
+    alive: Vec<bool>,
+    pos_faces: Vec<Vec<usize>>, // caras que tocan cada posición (pueden estar muertas)
+    quadrics: Vec<Quadric>,
+    stamps: Vec<u32>,
+    removed: Vec<bool>,
+}
+
+impl Simplifier {
+    fn new(mesh: &Mesh) -> Self {
+        // Agrupar esquinas por posición exacta
+        let mut by_bits: HashMap<[u32; 3], u32> = HashMap::new();
+        let mut points = Vec::new();
+        let corner_pos: Vec<u32> = mesh.positions.iter().map(|p| {
+            let key = [(p.x + 0.0).to_bits(), (p.y + 0.0).to_bits(), (p.z + 0.0).to_bits()];
+            *by_bits.entry(key).or_insert_with(|| {
+                points.push(glm::vec3(p.x as f64, p.y as f64, p.z as f64));
+                points.len() as u32 - 1
+            })
+        }).collect();
+
+        let n = points.len();
+        let mut s = Self {
+            points,
+            corner_pos,
+            faces: mesh.indices.clone(),
+            alive: vec![true; mesh.indices.len()],
+            pos_faces: vec![Vec::new(); n],
+            quadrics: vec![Quadric::default(); n],
+            stamps: vec![0; n],
+            removed: vec![false; n],
+        };
+
+        // Cuádricas de los planos de las caras (ponderadas por área)
+        for (f, face) in mesh.indices.iter().enumerate() {
+            let [a, b, c] = s.face_pos(face);
+            if a == b || b == c || a == c {
+                s.alive[f] = false;
+                continue;
+            }
+            for p in [a, b, c] {
+                s.pos_faces[p as usize].push(f);
+            }
+            let (pa, pb, pc) = (s.points[a as usize], s.points[b as usize], s.points[c as usize]);
+            let cross = (pb - pa).cross(&(pc - pa));
+            let area = cross.magnitude() * 0.5;
+            if area <= 1e-30 {
+                continue;
+            }
+            let n = cross.normalize();
+            let q = Quadric::plane(n, -n.dot(&pa), area);
+            for p in [a, b, c] {
+                s.quadrics[p as usize].add(&q);
+            }
+        }
+
+        // Bordes y costuras: aristas con una sola cara a cada lado de las
+        // esquinas. Se fijan con un plano perpendicular a la cara.
+        let mut edges: BTreeMap<(u32, u32), Vec<usize>> = BTreeMap::new();
+        for (f, face) in s.faces.iter().enumerate() {
+            if !s.alive[f] {
+                continue;
+            }
+            for k in 0..3 {
+                let (a, b) = (face[k], face[(k + 1) % 3]);
+                edges.entry((a.min(b), a.max(b))).or_default().push(f);
+            }
+        }
+        for (&(a, b), fs) in &edges {
+            if fs.len() != 1 {
+                continue;
+            }
+            let (pa, pb) = (s.points[s.corner_pos[a as usize] as usize], s.points[s.corner_pos[b as usize] as usize]);
+            let [x, y, z] = s.face_pos(&s.faces[fs[0]]).map(|p| s.points[p as usize]);
+            let face_n = (y - x).cross(&(z - x));
+            let edge = pb - pa;
+            let n = edge.cross(&face_n);
+            if n.magnitude() <= 1e-30 {
+                continue;
+            }
+            let n = n.normalize();
+            let q = Quadric::plane(n, -n.dot(&pa), BORDER_WEIGHT * edge.magnitude_squared());
+            s.quadrics[s.corner_pos[a as usize] as usize].add(&q);
+            s.quadrics[s.corner_pos[b as usize] as usize].add(&q);
+        }
+        s
+    }
+
+    fn face_pos(&self, face: &[u32; 3]) -> [u32; 3] {
+        face.map(|c| self.corner_pos[c as usize])
+    }
+
+    fn neighbors(&self, p: u32) -> Vec<u32> {
+        let mut out: Vec<u32> = self.pos_faces[p as usize]
+            .iter()
+            .filter(|&&f| self.alive[f])
+            .flat_map(|&f| self.face_pos(&self.faces[f]))
+            .filter(|&q| q != p)
+            .collect();
+        out.sort_unstable();
+        out.dedup();
+        out
+    }
+
+    fn push_edges(&self, heap: &mut BinaryHeap<Collapse>, p: u32) {
+        for q in self.neighbors(p) {
+            for (u, v) in [(p, q), (q, p)] {
+                let mut quadric = self.quadrics[u as usize];
+                quadric.add(&self.quadrics[v as usize]);
+                heap.push(Collapse {
+                    cost: quadric.error(self.points[v as usize]),
+                    u,
+                    v,
+                    stamps: (self.stamps[u as usize], self.stamps[v as usize]),
+                });
+            }
+        }
+    }
+
+    // Reasignación de las esquinas de u a esquinas de v, o None si el colapso
+    // rompería la topología, una costura o daría la vuelta a alguna cara
+    fn plan(&self, u: u32, v: u32) -> Option<HashMap<u32, u32>> {
+        let faces_u: Vec<usize> = self.pos_faces[u as usize].iter().copied().filter(|&f| self.alive[f]).collect();
+        let shared: Vec<usize> = faces_u.iter().copied()
+            .filter(|&f| self.face_pos(&self.faces[f]).contains(&v))
+            .collect();
+        if shared.is_empty() {
+            return None;
+        }
+
+        // Condición de enlace: los vecinos comunes son los opuestos a la arista
+        let nu = self.neighbors(u);
+        let nv = self.neighbors(v);
+        let common = nu.iter().filter(|q| nv.binary_search(q).is_ok()).count();
+        if common != shared.len() {
+            return None;
+        }
+
+        // Esquina de u → esquina de v en las caras que desaparecen
+        let mut remap: HashMap<u32, u32> = HashMap::new();
+        for &f in &shared {
+            let face = self.faces[f];
+            let cu = face.iter().copied().find(|&c| self.corner_pos[c as usize] == u)?;
+            let cv = face.iter().copied().find(|&c| self.corner_pos[c as usize] == v)?;
+            if *remap.entry(cu).or_insert(cv) != cv {
+                return None;
+            }
+        }
+
+        // Las caras que quedan: cada esquina de u debe tener destino y la cara no debe invertirse
+        let pv = self.points[v as usize];
+        for &f in &faces_u {
+            if shared.contains(&f) {
+                continue;
+            }
+            let face = self.faces[f];
+            if face.iter().any(|c| self.corner_pos[*c as usize] == u && !remap.contains_key(c)) {
+                return None;
+            }
+            let pts = self.face_pos(&face).map(|p| self.points[p as usize]);
+            let moved = self.face_pos(&face).map(|p| if p == u { pv } else { self.points[p as usize] });
+            let before = (pts[1] - pts[0]).cross(&(pts[2] - pts[0]));
+            let after = (moved[1] - moved[0]).cross(&(moved[2] - moved[0]));
+            if after.magnitude() <= 1e-30 || before.dot(&after) <= 0.2 * before.magnitude() * after.magnitude() {
+                return None;
+            }
+        }
+        Some(remap)
+    }
+
+    fn collapse(&mut self, u: u32, v: u32, remap: &HashMap<u32, u32>) -> usize {
+        let mut killed = 0;
+        let faces_u = std::mem::take(&mut self.pos_faces[u as usize]);
+        for f in faces_u {
+            if !self.alive[f] {
+                continue;
+            }
+            if self.face_pos(&self.faces[f]).contains(&v) {
+                self.alive[f] = false;
+                killed += 1;
+                continue;
+            }
+            for c in &mut self.faces[f] {
+                if let Some(&to) = remap.get(c) {
+                    *c = to;
+                }
+            }
+            self.pos_faces[v as usize].push(f);
+        }
+        let qu = self.quadrics[u as usize];
+        self.quadrics[v as usize].add(&qu);
+        self.removed[u as usize] = true;
+        self.stamps[v as usize] += 1;
+        killed
+    }
+}
+
+impl Mesh {
+    // Simplifica por colapso de aristas con error cuadrático hasta quedarse
+    // con unos `target_triangles` (o hasta que no quede colapso válido).
+    // Conserva las partes, UVs, normales y colores de los vértices que
+    // sobreviven; las tangentes se recalculan.
+    pub fn simplify(&mut self, target_triangles: usize) {
+        let had_tangents = !self.tangents.is_empty();
+        self.weld_identical_vertices();
+
+        let mut s = Simplifier::new(self);
+        let mut remaining = s.alive.iter().filter(|&&a| a).count();
+
+        let mut heap = BinaryHeap::new();
+        for p in 0..s.points.len() as u32 {
+            s.push_edges(&mut heap, p);
+        }
+        while remaining > target_triangles {
+            let Some(c) = heap.pop() else { break };
+            if s.removed[c.u as usize] || s.removed[c.v as usize]
+                || c.stamps != (s.stamps[c.u as usize], s.stamps[c.v as usize]) {
+                continue;
+            }
+            let Some(remap) = s.plan(c.u, c.v) else { continue };
+            remaining -= s.collapse(c.u, c.v, &remap);
+            s.push_edges(&mut heap, c.v);
+        }
+
+        // Reconstruir índices por parte y descartar esquinas sin uso
+        let mut sources: Vec<u32> = Vec::new();
+        let mut new_index: HashMap<u32, u32> = HashMap::new();
+        let mut indices = Vec::with_capacity(remaining);
+        for sub in &mut self.submeshes {
+            let start = indices.len();
+            for f in sub.start..sub.start + sub.count {
+                if !s.alive[f] {
+                    continue;
+                }
+                indices.push(s.faces[f].map(|c| *new_index.entry(c).or_insert_with(|| {
+                    sources.push(c);
+                    sources.len() as u32 - 1
+                })));
+            }
+            sub.start = start;
+            sub.count = indices.len() - start;
+        }
+
+        self.reindex_vertices(&sources);
+        self.indices = indices;
+        self.compute_bounds();
+        self.clusters.clear();
+        if had_tangents {
+            self.compute_tangents().ok();
+        }
+    }
+
+    // Une vértices con la misma posición, normal, UV y color: los que solo
+    // difieren en la tangente (división de MikkTSpace) no son costuras.
+    // Cada parte conserva sus vértices. Descarta las tangentes.
+    fn weld_identical_vertices(&mut self) {
+        let bits = |v: &[f32]| v.iter().map(|x| (x + 0.0).to_bits()).collect::<Vec<u32>>();
+        let parts = self.vertex_parts();
+        let mut seen: HashMap<(usize, Vec<u32>), u32> = HashMap::new();
+        let mut sources: Vec<u32> = Vec::new();
+        let remap: Vec<u32> = (0..self.positions.len()).map(|i| {
+            let mut key = bits(self.positions[i].as_slice());
+            key.extend(bits(self.normals[i].as_slice()));
+            if let Some(t) = self.uvs.get(i) { key.extend(bits(t.as_slice())); }
+            if let Some(c) = self.colors.get(i) { key.extend(bits(c.as_slice())); }
+            *seen.entry((parts[i], key)).or_insert_with(|| {
+                sources.push(i as u32);
+                sources.len() as u32 - 1
+            })
+        }).collect();
+
+        self.tangents.clear();
+        self.reindex_vertices(&sources);
+        for t in &mut self.indices {
+            *t = t.map(|v| remap[v as usize]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::SubMesh;
+
+    // Rejilla plana de n×n celdas desde `x0`, con sus propios vértices y UVs
+    // de 0 a 1: dos rejillas contiguas forman una costura de UV en la unión
+    fn grid(x0: f32, n: u32, positions: &mut Vec<glm::Vec3>, uvs: &mut Vec<glm::Vec2>, indices: &mut Vec<[u32; 3]>) {
+        let base = positions.len() as u32;
+        for j in 0..=n {
+            for i in 0..=n {
+                positions.push(glm::vec3(x0 + i as f32, j as f32, 0.0));
+                uvs.push(glm::vec2(i as f32 / n as f32, j as f32 / n as f32));
+            }
+        }
+        let at = |i: u32, j: u32| base + j * (n + 1) + i;
+        for j in 0..n {
+            for i in 0..n {
+                indices.push([at(i, j), at(i + 1, j), at(i + 1, j + 1)]);
+                indices.push([at(i, j), at(i + 1, j + 1), at(i, j + 1)]);
+            }
+        }
+    }
+
+    #[test]
+    fn simplify_keeps_vertices_per_part() {
+        let (mut positions, mut uvs, mut indices) = (Vec::new(), Vec::new(), Vec::new());
+        grid(0.0, 4, &mut positions, &mut uvs, &mut indices);
+        let half = indices.len();
+        grid(4.0, 4, &mut positions, &mut uvs, &mut indices);
+        let normals = vec![glm::vec3(0.0, 0.0, 1.0); positions.len()];
+        let mut mesh = Mesh::new(positions, normals, uvs, indices, 1.0);
+        mesh.submeshes = vec![SubMesh::new("a", 0, half, None), SubMesh::new("b", half, half, None)];
+
+        mesh.simplify(half);
+        assert!(!mesh.indices.is_empty() && mesh.indices.len() <= half, "quedan {} triángulos", mesh.indices.len());
+
+        let parts = mesh.vertex_parts();
+        for (p, sub) in mesh.submeshes.iter().enumerate() {
+            assert!(sub.count > 0);
+            let x0 = p as f32 * 4.0;
+            let faces = &mesh.indices[sub.start..sub.start + sub.count];
+            for &v in faces.iter().flatten() {
+                assert_eq!(parts[v as usize], p, "vértice {} compartido entre partes", v);
+                // Cada vértice conserva la UV que corresponde a su posición
+                let (pos, uv) = (mesh.positions[v as usize], mesh.uvs[v as usize]);
+                assert_eq!(uv, glm::vec2((pos.x - x0) / 4.0, pos.y / 4.0), "UV movida en {}", v);
+            }
+
+            // La parte sigue cubriendo su cuadrado entero, sin caras giradas:
+            // el borde exterior y la costura con la otra parte no se han movido
+            let mut area = 0.0;
+            for f in faces {
+                let [a, b, c] = f.map(|v| mesh.positions[v as usize]);
+                let z = (b - a).cross(&(c - a)).z * 0.5;
+                assert!(z > 0.0, "cara degenerada o girada en la parte {}", p);
+                area += z;
+            }
+            assert!((area - 16.0).abs() < 1e-4, "área de la parte {}: {}", p, area);
+            for corner in [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)] {
+                let corner = glm::vec3(x0 + corner.0, corner.1, 0.0);
+                assert!(faces.iter().flatten().any(|&v| mesh.positions[v as usize] == corner),
+                    "falta la esquina {:?} de la parte {}", corner, p);
+            }
+        }
+    }
+}
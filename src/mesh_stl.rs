@@ -0,0 +1,144 @@
+use nalgebra_glm as glm;
+use std::collections::HashMap;
+
+use crate::mesh::{LoadOptions, Mesh};
+
+// Triángulos sueltos de STL (binario o ASCII), sin índices
+fn parse_stl(bytes: &[u8]) -> Result<Vec<[glm::Vec3; 3]>, String> {
+    // Un binario puede empezar también por "solid": se decide por el tamaño
+    let binary_len = |n: usize| 84 + n * 50;
+    if bytes.len() >= 84 {
+        let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+        if binary_len(count) == bytes.len() || !bytes.starts_with(b"solid") {
+            return parse_stl_binary(bytes, count);
+        }
+    }
+    parse_stl_ascii(std::str::from_utf8(bytes).map_err(|_| "STL ASCII con bytes no válidos".to_string())?)
+}
+
+fn parse_stl_binary(bytes: &[u8], count: usize) -> Result<Vec<[glm::Vec3; 3]>, String> {
+    if bytes.len() < 84 + count * 50 {
+        return Err(format!("STL binario truncado ({} triángulos declarados)", count));
+    }
+    let f = |o: usize| f32::from_le_bytes([bytes[o], bytes[o + 1], bytes[o + 2], bytes[o + 3]]);
+    let v = |o: usize| glm::vec3(f(o), f(o + 4), f(o + 8));
+    // Por triángulo: normal (12), 3 vértices (36), atributo (2)
+    Ok((0..count)
+        .map(|i| {
+            let o = 84 + i * 50 + 12;
+            [v(o), v(o + 12), v(o + 24)]
+        })
+        .collect())
+}
+
+fn parse_stl_ascii(text: &str) -> Result<Vec<[glm::Vec3; 3]>, String> {
+    let mut tris = Vec::new();
+    let mut corners: Vec<glm::Vec3> = Vec::with_capacity(3);
+    for (n, line) in text.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("vertex") => {
+                let c: Vec<f32> = words.map(|w| w.parse::<f32>()).collect::<Result<_, _>>()
+                    .map_err(|_| format!("vértice inválido en la línea {}", n + 1))?;
+                if c.len() != 3 {
+                    return Err(format!("vértice inválido en la línea {}", n + 1));
+                }
+                corners.push(glm::vec3(c[0], c[1], c[2]));
+            }
+            Some("endloop") => {
+                // Polígonos de más de 3 vértices (poco comunes): abanico
+                for k in 1..corners.len().saturating_sub(1) {
+                    tris.push([corners[0], corners[k], corners[k + 1]]);
+                }
+                corners.clear();
+            }
+            _ => {}
+        }
+    }
+    Ok(tris)
+}
+
+impl Mesh {
+    // STL: los triángulos no comparten vértices, así que se sueldan por
+    // posición exacta antes de generar normales (las del archivo son planas).
+    pub fn load_stl_with(path: &str, target_pixels: f32, opts: &LoadOptions) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Error cargando '{}': {}", path, e))?;
+        let tris = parse_stl(&bytes).map_err(|e| format!("Error cargando '{}': {}", path, e))?;
+
+        let mut weld: HashMap<[u32; 3], u32> = HashMap::new();
+        let mut positions: Vec<glm::Vec3> = Vec::new();
+        let mut indices: Vec<[u32; 3]> = Vec::with_capacity(tris.len());
+        for tri in &tris {
+            if tri.iter().any(|p| !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite())) {
+                continue;
+            }
+            let ids = tri.map(|p| {
+                // +0.0 convierte -0.0 en 0.0 para que ambos se suelden
+                let key = [(p.x + 0.0).to_bits(), (p.y + 0.0).to_bits(), (p.z + 0.0).to_bits()];
+                *weld.entry(key).or_insert_with(|| {
+                    positions.push(p);
+                    positions.len() as u32 - 1
+                })
+            });
+            // Triángulos degenerados por la soldadura
+            if ids[0] != ids[1] && ids[1] != ids[2] && ids[0] != ids[2] {
+                indices.push(ids);
+            }
+        }
+
+        if positions.is_empty() || indices.is_empty() {
+            return Err(format!("STL vacío o sin triángulos: '{}'", path));
+        }
+
+        let normals = vec![glm::Vec3::zeros(); positions.len()];
+        let mut mesh = Self::new(positions, normals, Vec::new(), indices, target_pixels);
+        if let Some(weld) = &opts.weld {
+            mesh.weld_vertices(weld);
+        }
+        mesh.compute_normals(opts.normals);
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLE: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+
+    fn binary(header: &[u8]) -> Vec<u8> {
+        let mut bytes = header.to_vec();
+        bytes.resize(80, b' ');
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend([0u8; 12]); // Normal
+        for c in TRIANGLE.iter().flatten() {
+            bytes.extend(c.to_le_bytes());
+        }
+        bytes.extend([0u8; 2]);
+        bytes
+    }
+
+    fn check(tris: &[[glm::Vec3; 3]]) {
+        assert_eq!(tris.len(), 1);
+        assert_eq!(tris[0][1], glm::vec3(1.0, 0.0, 0.0));
+        assert_eq!(tris[0][2], glm::vec3(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn ascii() {
+        let text = "solid prueba\n facet normal 0 0 1\n  outer loop\n   vertex 0 0 0\n   vertex 1 0 0\n\
+            vertex 0 1 0\n  endloop\n endfacet\nendsolid prueba\n";
+        check(&parse_stl(text.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn binary_detected() {
+        check(&parse_stl(&binary(b"exportado")).unwrap());
+    }
+
+    #[test]
+    fn binary_starting_with_solid() {
+        // Algunos exportadores escriben "solid" en la cabecera binaria: decide el tamaño
+        check(&parse_stl(&binary(b"solid exportado")).unwrap());
+    }
+}
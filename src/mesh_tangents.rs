@@ -0,0 +1,124 @@
+use nalgebra_glm as glm;
+use std::collections::HashMap;
+
+use crate::mesh::Mesh;
+
+// Adaptador de la malla para mikktspace: una cara por triángulo y la tangente
+// resultante guardada por esquina (luego se parten los vértices que difieran).
+struct MikkInput<'a> {
+    mesh: &'a Mesh,
+    corners: Vec<[f32; 4]>,
+}
+
+impl bevy_mikktspace::Geometry for MikkInput<'_> {
+    fn num_faces(&self) -> usize {
+        self.mesh.indices.len()
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        3
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        let p = self.mesh.positions[self.mesh.indices[face][vert] as usize];
+        [p.x, p.y, p.z]
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        let n = self.mesh.normals[self.mesh.indices[face][vert] as usize];
+        [n.x, n.y, n.z]
+    }
+
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        let t = self.mesh.uvs[self.mesh.indices[face][vert] as usize];
+        [t.x, t.y]
+    }
+
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        self.corners[face * 3 + vert] = tangent;
+    }
+}
+
+impl Mesh {
+    // Tangentes compatibles con MikkTSpace (xyz + signo de la bitangente en w,
+    // B = cross(N, T) * w). Requiere UVs; sin ellas deja `tangents` vacío.
+    pub fn compute_tangents(&mut self) -> Result<(), String> {
+        self.tangents.clear();
+        if self.uvs.is_empty() {
+            return Err("la malla no tiene UVs".into());
+        }
+
+        let mut input = MikkInput { mesh: self, corners: vec![[1.0, 0.0, 0.0, 1.0]; self.indices.len() * 3] };
+        if !bevy_mikktspace::generate_tangents(&mut input) {
+            return Err("mikktspace no pudo generar tangentes".into());
+        }
+        let corners = input.corners;
+
+        // Partir vértices cuyas esquinas recibieron tangentes distintas
+        let mut split: HashMap<(u32, [u32; 4]), u32> = HashMap::new();
+        let mut sources: Vec<u32> = Vec::new();
+        let mut tangents: Vec<glm::Vec4> = Vec::new();
+        let mut new_indices = Vec::with_capacity(self.indices.len());
+
+        for (f, t) in self.indices.iter().enumerate() {
+            let mut tri = [0u32; 3];
+            for (k, &v) in t.iter().enumerate() {
+                let c = corners[f * 3 + k];
+                let key = [c[0].to_bits(), c[1].to_bits(), c[2].to_bits(), c[3].to_bits()];
+                tri[k] = *split.entry((v, key)).or_insert_with(|| {
+                    sources.push(v);
+                    tangents.push(glm::vec4(c[0], c[1], c[2], if c[3] < 0.0 { -1.0 } else { 1.0 }));
+                    sources.len() as u32 - 1
+                });
+            }
+            new_indices.push(tri);
+        }
+
+        self.reindex_vertices(&sources);
+        self.tangents = tangents;
+        self.indices = new_indices;
+        Ok(())
+    }
+
+    // UVs esféricas (equirectangulares) alrededor del centro, para mallas que
+    // no las traen. Los triángulos que cruzan la costura u=0/1 reciben copias
+    // de sus vértices con u desplazada para no interpolar a través del mapa.
+    pub fn compute_spherical_uvs(&mut self) {
+        let uv_of = |p: glm::Vec3| {
+            let d = p - self.center;
+            let d = if d.magnitude() > 1e-12 { d.normalize() } else { glm::vec3(0.0, 1.0, 0.0) };
+            let u = d.x.atan2(-d.z) / std::f32::consts::TAU + 0.5;
+            let v = 1.0 - d.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI; // v hacia arriba, como en OBJ
+            glm::vec2(u, v)
+        };
+        let base: Vec<glm::Vec2> = self.positions.iter().map(|p| uv_of(*p)).collect();
+
+        let mut split: HashMap<(u32, bool), u32> = HashMap::new();
+        let mut sources: Vec<u32> = Vec::new();
+        let mut uvs: Vec<glm::Vec2> = Vec::new();
+        let mut new_indices = Vec::with_capacity(self.indices.len());
+
+        for t in &self.indices {
+            let us = t.map(|v| base[v as usize].x);
+            let crosses = us.iter().cloned().fold(f32::MIN, f32::max) - us.iter().cloned().fold(f32::MAX, f32::min) > 0.5;
+            let mut tri = [0u32; 3];
+            for (k, &v) in t.iter().enumerate() {
+                let shifted = crosses && us[k] < 0.5;
+                tri[k] = *split.entry((v, shifted)).or_insert_with(|| {
+                    let mut uv = base[v as usize];
+                    if shifted { uv.x += 1.0; }
+                    sources.push(v);
+                    uvs.push(uv);
+                    sources.len() as u32 - 1
+                });
+            }
+            new_indices.push(tri);
+        }
+
+        self.uvs.clear();
+        self.tangents.clear();
+        self.reindex_vertices(&sources);
+        self.uvs = uvs;
+        self.indices = new_indices;
+    }
+}
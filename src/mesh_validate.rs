@@ -0,0 +1,439 @@
+use nalgebra_glm as glm;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::mesh::Mesh;
+use crate::mesh_bounds::Bounds;
+
+// Resultado de `Mesh::validate`. La topología (aristas, agujeros, orden de
+// los vértices) se mide por posición, así que las costuras de UV o normales
+// no cuentan como bordes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ValidationReport {
+    pub vertices: usize,
+    pub triangles: usize,
+    pub non_finite_vertices: usize,  // NaN o infinito en posición, normal o UV
+    pub out_of_range: usize,         // triángulos con índices fuera de rango (los cargadores ya los rechazan)
+    pub degenerate: usize,           // índices repetidos o área nula
+    pub non_manifold_edges: usize,   // aristas con más de dos caras
+    pub boundary_edges: usize,       // aristas con una sola cara
+    pub holes: usize,                // lazos de borde
+    pub inconsistent_winding: usize, // aristas recorridas igual por sus dos caras
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.non_finite_vertices == 0
+            && self.out_of_range == 0
+            && self.degenerate == 0
+            && self.non_manifold_edges == 0
+            && self.holes == 0
+            && self.inconsistent_winding == 0
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} vértices, {} triángulos", self.vertices, self.triangles)?;
+        let rows = [
+            ("vértices con NaN/infinito", self.non_finite_vertices),
+            ("triángulos con índices fuera de rango", self.out_of_range),
+            ("triángulos degenerados", self.degenerate),
+            ("aristas no manifold", self.non_manifold_edges),
+            ("aristas de borde", self.boundary_edges),
+            ("agujeros", self.holes),
+            ("aristas con orden inconsistente", self.inconsistent_winding),
+        ];
+        for (label, n) in rows {
+            writeln!(f, "  {:<40} {}", label, n)?;
+        }
+        write!(f, "  {}", if self.is_clean() { "✓ malla válida" } else { "✗ malla con problemas" })
+    }
+}
+
+// Reparaciones de `Mesh::repair`
+#[derive(Clone, Copy, Debug)]
+pub struct RepairOptions {
+    pub drop_invalid: bool,    // quitar triángulos fuera de rango o con NaN
+    pub drop_degenerate: bool, // quitar triángulos sin área
+    pub unify_winding: bool,   // orientar las caras como sus vecinas (y hacia fuera si es cerrada)
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        Self { drop_invalid: true, drop_degenerate: true, unify_winding: true }
+    }
+}
+
+// Conjuntos disjuntos para contar lazos de borde
+fn find(parent: &mut [u32], mut x: u32) -> u32 {
+    while parent[x as usize] != x {
+        parent[x as usize] = parent[parent[x as usize] as usize];
+        x = parent[x as usize];
+    }
+    x
+}
+
+impl Mesh {
+    // ¿Posición, normal y UV del vértice sin NaN ni infinitos?
+    fn finite_vertices(&self) -> Vec<bool> {
+        (0..self.positions.len())
+            .map(|i| {
+                let p = self.positions[i];
+                let n = self.normals.get(i).copied().unwrap_or_else(glm::Vec3::zeros);
+                let t = self.uvs.get(i).copied().unwrap_or_else(glm::Vec2::zeros);
+                p.iter().chain(n.iter()).chain(t.iter()).all(|x| x.is_finite())
+            })
+            .collect()
+    }
+
+    // Área mínima para no considerar un triángulo degenerado, según la caja
+    // de los vértices finitos
+    fn min_area(&self) -> f32 {
+        let b = Bounds::from_points(self.positions.iter().filter(|p| p.iter().all(|x| x.is_finite())));
+        ((b.max - b.min).magnitude() * 1e-7).powi(2)
+    }
+
+    fn triangle_area(&self, t: &[u32; 3]) -> f32 {
+        let [a, b, c] = t.map(|i| self.positions[i as usize]);
+        (b - a).cross(&(c - a)).magnitude() * 0.5
+    }
+
+    // Índices repetidos o área nula (o NaN)
+    fn is_degenerate(&self, t: &[u32; 3], min_area: f32) -> bool {
+        let area = self.triangle_area(t);
+        t[0] == t[1] || t[1] == t[2] || t[0] == t[2] || area.is_nan() || area <= min_area
+    }
+
+    // Posición soldada de cada vértice (por bits exactos)
+    fn position_ids(&self) -> Vec<u32> {
+        let mut ids: HashMap<[u32; 3], u32> = HashMap::new();
+        self.positions
+            .iter()
+            .map(|p| {
+                let key = [(p.x + 0.0).to_bits(), (p.y + 0.0).to_bits(), (p.z + 0.0).to_bits()];
+                let next = ids.len() as u32;
+                *ids.entry(key).or_insert(next)
+            })
+            .collect()
+    }
+
+    // Triángulos válidos con sus esquinas en posiciones soldadas
+    fn welded_faces(&self, pos_id: &[u32], finite: &[bool]) -> Vec<(usize, [u32; 3])> {
+        let min_area = self.min_area();
+        self.indices
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.iter().all(|&i| finite.get(i as usize) == Some(&true)) && !self.is_degenerate(t, min_area))
+            .map(|(f, t)| (f, t.map(|i| pos_id[i as usize])))
+            .filter(|(_, w)| w[0] != w[1] && w[1] != w[2] && w[0] != w[2])
+            .collect()
+    }
+
+    pub fn validate(&self) -> ValidationReport {
+        let n = self.positions.len();
+        let finite = self.finite_vertices();
+        let mut r = ValidationReport {
+            vertices: n,
+            triangles: self.indices.len(),
+            non_finite_vertices: finite.iter().filter(|&&ok| !ok).count(),
+            ..Default::default()
+        };
+
+        let min_area = self.min_area();
+        for t in &self.indices {
+            if t.iter().any(|&i| i as usize >= n) {
+                r.out_of_range += 1;
+            } else if t.iter().all(|&i| finite[i as usize]) && self.is_degenerate(t, min_area) {
+                r.degenerate += 1;
+            }
+        }
+
+        // Aristas por posición: cuántas caras y en qué sentido las recorren
+        let pos_id = self.position_ids();
+        let mut edges: HashMap<(u32, u32), (u32, u32)> = HashMap::new(); // (a→b, b→a) con a < b
+        for (_, w) in self.welded_faces(&pos_id, &finite) {
+            for k in 0..3 {
+                let (a, b) = (w[k], w[(k + 1) % 3]);
+                let e = edges.entry((a.min(b), a.max(b))).or_default();
+                if a < b { e.0 += 1 } else { e.1 += 1 }
+            }
+        }
+
+        let mut parent: Vec<u32> = (0..n as u32).collect();
+        let mut boundary_vertices = Vec::new();
+        for (&(a, b), &(fwd, back)) in &edges {
+            match fwd + back {
+                1 => {
+                    r.boundary_edges += 1;
+                    let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+                    parent[ra as usize] = rb;
+                    boundary_vertices.push(a);
+                }
+                2 if fwd != 1 => r.inconsistent_winding += 1,
+                2 => {}
+                _ => r.non_manifold_edges += 1,
+            }
+        }
+        let mut roots: Vec<u32> = boundary_vertices.iter().map(|&v| find(&mut parent, v)).collect();
+        roots.sort_unstable();
+        roots.dedup();
+        r.holes = roots.len();
+        r
+    }
+
+    // Aplica las reparaciones pedidas; devuelve cuántos triángulos se
+    // quitaron y cuántos se voltearon. Los vértices que quedan sin usar
+    // se eliminan.
+    pub fn repair(&mut self, opts: &RepairOptions) -> (usize, usize) {
+        let n = self.positions.len();
+        let min_area = self.min_area();
+        let finite = self.finite_vertices();
+        let keep = |t: &[u32; 3]| {
+            let invalid = t.iter().any(|&i| i as usize >= n || !finite[i as usize]);
+            if invalid {
+                return !opts.drop_invalid;
+            }
+            let degenerate = self.is_degenerate(t, min_area);
+            !(degenerate && opts.drop_degenerate)
+        };
+
+        let kept: Vec<bool> = self.indices.iter().map(keep).collect();
+
+        let mut indices = Vec::with_capacity(self.indices.len());
+        let before = self.indices.len();
+        for sub in &mut self.submeshes {
+            let start = indices.len();
+            indices.extend((sub.start..sub.start + sub.count).filter(|&f| kept[f]).map(|f| self.indices[f]));
+            sub.start = start;
+            sub.count = indices.len() - start;
+        }
+        self.indices = indices;
+        let dropped = before - self.indices.len();
+        if dropped > 0 {
+            self.drop_unused_vertices();
+        }
+
+        let flipped = if opts.unify_winding { self.unify_winding() } else { 0 };
+        if dropped + flipped > 0 {
+            self.compute_bounds();
+            self.clusters.clear();
+            if !self.tangents.is_empty() {
+                self.compute_tangents().ok();
+            }
+        }
+        (dropped, flipped)
+    }
+
+    fn drop_unused_vertices(&mut self) {
+        let n = self.positions.len();
+        let mut used = vec![false; n];
+        for t in &self.indices {
+            for &i in t {
+                if let Some(u) = used.get_mut(i as usize) {
+                    *u = true;
+                }
+            }
+        }
+        let sources: Vec<u32> = (0..n as u32).filter(|&i| used[i as usize]).collect();
+        if sources.len() == n {
+            return;
+        }
+        let mut remap = vec![0u32; n];
+        for (new, &old) in sources.iter().enumerate() {
+            remap[old as usize] = new as u32;
+        }
+        for t in &mut self.indices {
+            *t = t.map(|i| remap[i as usize]);
+        }
+        self.reindex_vertices(&sources);
+    }
+
+    // Recorre cada componente por aristas manifold y voltea las caras que no
+    // siguen el sentido de su vecina. Si la componente es cerrada y su
+    // volumen sale negativo, se voltea entera para que mire hacia fuera.
+    fn unify_winding(&mut self) -> usize {
+        let pos_id = self.position_ids();
+        let faces = self.welded_faces(&pos_id, &self.finite_vertices());
+        let mut edge_faces: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+        for (k, (_, w)) in faces.iter().enumerate() {
+            for j in 0..3 {
+                let (a, b) = (w[j], w[(j + 1) % 3]);
+                edge_faces.entry((a.min(b), a.max(b))).or_default().push(k);
+            }
+        }
+        // ¿Recorre la cara k la arista a→b?
+        let has_directed = |w: &[u32; 3], a: u32, b: u32| (0..3).any(|j| w[j] == a && w[(j + 1) % 3] == b);
+
+        let mut flip = vec![false; faces.len()];
+        let mut seen = vec![false; faces.len()];
+        let mut flipped = 0;
+        for seed in 0..faces.len() {
+            if seen[seed] {
+                continue;
+            }
+            seen[seed] = true;
+            let mut component = vec![seed];
+            let mut closed = true;
+            let mut queue = VecDeque::from([seed]);
+            while let Some(k) = queue.pop_front() {
+                let mut w = faces[k].1;
+                if flip[k] {
+                    w.swap(1, 2);
+                }
+                for j in 0..3 {
+                    let (a, b) = (w[j], w[(j + 1) % 3]);
+                    let adj = &edge_faces[&(a.min(b), a.max(b))];
+                    if adj.len() != 2 {
+                        closed = false;
+                        continue;
+                    }
+                    let other = if adj[0] == k { adj[1] } else { adj[0] };
+                    if seen[other] {
+                        continue;
+                    }
+                    seen[other] = true;
+                    // La vecina debe recorrer la arista compartida al revés
+                    flip[other] = has_directed(&faces[other].1, a, b);
+                    component.push(other);
+                    queue.push_back(other);
+                }
+            }
+
+            if closed {
+                let volume: f32 = component.iter().map(|&k| {
+                    let mut t = self.indices[faces[k].0];
+                    if flip[k] {
+                        t.swap(1, 2);
+                    }
+                    let [a, b, c] = t.map(|i| self.positions[i as usize]);
+                    a.dot(&b.cross(&c))
+                }).sum();
+                if volume < 0.0 {
+                    for &k in &component {
+                        flip[k] = !flip[k];
+                    }
+                }
+            }
+            for &k in &component {
+                if flip[k] {
+                    self.indices[faces[k].0].swap(1, 2);
+                    flipped += 1;
+                }
+            }
+        }
+        flipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tetraedro cerrado con las caras hacia fuera (volumen positivo)
+    const TETRA: [[u32; 3]; 4] = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+
+    fn mesh(extra: &[glm::Vec3], indices: &[[u32; 3]]) -> Mesh {
+        let mut positions = vec![
+            glm::vec3(0.0, 0.0, 0.0), glm::vec3(1.0, 0.0, 0.0), glm::vec3(0.0, 1.0, 0.0), glm::vec3(0.0, 0.0, 1.0),
+        ];
+        positions.extend_from_slice(extra);
+        let normals = vec![glm::Vec3::zeros(); positions.len()];
+        Mesh::new(positions, normals, Vec::new(), indices.to_vec(), 1.0)
+    }
+
+    fn with(extra: &[[u32; 3]]) -> Vec<[u32; 3]> {
+        TETRA.iter().chain(extra).copied().collect()
+    }
+
+    // Seis veces el volumen con signo
+    fn volume(m: &Mesh) -> f32 {
+        m.indices.iter().map(|t| {
+            let [a, b, c] = t.map(|i| m.positions[i as usize]);
+            a.dot(&b.cross(&c))
+        }).sum()
+    }
+
+    #[test]
+    fn closed_mesh_is_clean() {
+        let r = mesh(&[], &TETRA).validate();
+        assert!(r.is_clean());
+        assert_eq!((r.vertices, r.triangles, r.boundary_edges, r.holes), (4, 4, 0, 0));
+    }
+
+    #[test]
+    fn degenerate_triangles() {
+        // Índice repetido y tres puntos alineados
+        let r = mesh(&[glm::vec3(2.0, 0.0, 0.0)], &with(&[[0, 0, 1], [0, 1, 4]])).validate();
+        assert_eq!(r.degenerate, 2);
+        assert_eq!((r.non_manifold_edges, r.boundary_edges), (0, 0), "los degenerados no cuentan como aristas");
+        assert!(!r.is_clean());
+    }
+
+    #[test]
+    fn out_of_range_index() {
+        let r = mesh(&[], &with(&[[0, 1, 99]])).validate();
+        assert_eq!(r.out_of_range, 1);
+        assert_eq!(r.degenerate, 0);
+    }
+
+    #[test]
+    fn nan_position() {
+        let r = mesh(&[glm::vec3(f32::NAN, 0.0, 0.0)], &with(&[[0, 1, 4]])).validate();
+        assert_eq!(r.non_finite_vertices, 1);
+        assert_eq!((r.degenerate, r.boundary_edges), (0, 0));
+    }
+
+    #[test]
+    fn open_boundary() {
+        let r = mesh(&[], &TETRA[..3]).validate();
+        assert_eq!((r.boundary_edges, r.holes), (3, 1));
+        assert_eq!(r.inconsistent_winding, 0);
+    }
+
+    #[test]
+    fn non_manifold_edge() {
+        // Tercera cara sobre la arista 0-1
+        let r = mesh(&[glm::vec3(0.5, -1.0, 0.5)], &with(&[[1, 0, 4]])).validate();
+        assert_eq!(r.non_manifold_edges, 1);
+        assert_eq!((r.boundary_edges, r.holes), (2, 1));
+    }
+
+    #[test]
+    fn flipped_face() {
+        let mut indices = TETRA.to_vec();
+        indices[3] = [1, 3, 2];
+        let mut m = mesh(&[], &indices);
+        assert_eq!(m.validate().inconsistent_winding, 3);
+
+        assert_eq!(m.repair(&RepairOptions::default()), (0, 1));
+        assert!(m.validate().is_clean());
+        assert!(volume(&m) > 0.0);
+    }
+
+    #[test]
+    fn repair_turns_inside_out_mesh_outward() {
+        let inward: Vec<[u32; 3]> = TETRA.iter().map(|t| [t[0], t[2], t[1]]).collect();
+        let mut m = mesh(&[], &inward);
+        assert!(m.validate().is_clean(), "orden coherente aunque mire hacia dentro");
+        assert!(volume(&m) < 0.0);
+
+        assert_eq!(m.repair(&RepairOptions::default()), (0, 4));
+        assert!(volume(&m) > 0.0);
+    }
+
+    #[test]
+    fn repair_drops_invalid_triangles_and_unused_vertices() {
+        let extra = [glm::vec3(f32::NAN, 0.0, 0.0), glm::vec3(2.0, 0.0, 0.0)];
+        let mut m = mesh(&extra, &with(&[[0, 1, 99], [0, 1, 4], [0, 1, 5]]));
+        let (dropped, flipped) = m.repair(&RepairOptions::default());
+        assert_eq!((dropped, flipped), (3, 0));
+        assert_eq!((m.positions.len(), m.indices.len()), (4, 4));
+        assert_eq!(m.submeshes[0].count, 4);
+
+        let r = m.validate();
+        assert!(r.is_clean());
+        assert_eq!((r.boundary_edges, r.holes), (0, 0));
+        assert!(volume(&m) > 0.0);
+    }
+}
@@ -0,0 +1,139 @@
+use nalgebra_glm as glm;
+use std::collections::HashMap;
+
+use crate::mesh::Mesh;
+use crate::mesh_bounds::Bounds;
+
+// Tolerancias para unir vértices. La de posición es relativa a la diagonal
+// de la caja envolvente; las demás son absolutas por componente.
+#[derive(Clone, Copy, Debug)]
+pub struct WeldOptions {
+    pub position: f32,
+    pub normal: f32,
+    pub uv: f32,
+}
+
+impl Default for WeldOptions {
+    fn default() -> Self {
+        Self { position: 1e-6, normal: 1e-3, uv: 1e-5 }
+    }
+}
+
+fn close(a: &[f32], b: &[f32], eps: f32) -> bool {
+    a.iter().zip(b).all(|(x, y)| (x - y).abs() <= eps)
+}
+
+impl Mesh {
+    // Une vértices casi iguales (posición, normal, UV, color y tangente si
+    // las hay) y reconstruye `indices`. Los triángulos que quedan con dos
+    // esquinas iguales se descartan. Solo se unen vértices de la misma parte.
+    // Devuelve cuántos vértices se quitaron.
+    pub fn weld_vertices(&mut self, opts: &WeldOptions) -> usize {
+        let n = self.positions.len();
+        let b = Bounds::from_points(self.positions.iter());
+        let eps = (b.max - b.min).magnitude() * opts.position;
+        // Rejilla de celdas de tamaño eps: los candidatos están en las 27 vecinas
+        let cell = if eps > 0.0 { eps } else { f32::MIN_POSITIVE };
+        let cell_of = |p: &glm::Vec3| [(p.x / cell).floor() as i64, (p.y / cell).floor() as i64, (p.z / cell).floor() as i64];
+
+        let same = |i: usize, j: usize| {
+            close(self.positions[i].as_slice(), self.positions[j].as_slice(), eps)
+                && close(self.normals[i].as_slice(), self.normals[j].as_slice(), opts.normal)
+                && self.uvs.get(i).zip(self.uvs.get(j)).is_none_or(|(a, b)| close(a.as_slice(), b.as_slice(), opts.uv))
+                && self.colors.get(i).zip(self.colors.get(j)).is_none_or(|(a, b)| close(a.as_slice(), b.as_slice(), 1e-4))
+                && self.tangents.get(i).zip(self.tangents.get(j)).is_none_or(|(a, b)| a.w == b.w && close(a.as_slice(), b.as_slice(), opts.normal))
+        };
+
+        let parts = self.vertex_parts();
+        let mut grid: HashMap<(usize, [i64; 3]), Vec<u32>> = HashMap::new();
+        let mut sources: Vec<u32> = Vec::new();
+        let remap: Vec<u32> = (0..n).map(|i| {
+            let part = parts[i];
+            let c = cell_of(&self.positions[i]);
+            let mut found = None;
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let Some(reps) = grid.get(&(part, [c[0] + dx, c[1] + dy, c[2] + dz])) else { continue };
+                        if let Some(&r) = reps.iter().find(|&&r| same(sources[r as usize] as usize, i)) {
+                            found = Some(r);
+                            break 'search;
+                        }
+                    }
+                }
+            }
+            found.unwrap_or_else(|| {
+                sources.push(i as u32);
+                let id = sources.len() as u32 - 1;
+                grid.entry((part, c)).or_default().push(id);
+                id
+            })
+        }).collect();
+
+        // Índices nuevos, sin los triángulos que se hayan aplastado
+        let mut indices = Vec::with_capacity(self.indices.len());
+        for sub in &mut self.submeshes {
+            let start = indices.len();
+            for t in &self.indices[sub.start..sub.start + sub.count] {
+                let t = t.map(|v| remap[v as usize]);
+                if t[0] != t[1] && t[1] != t[2] && t[0] != t[2] {
+                    indices.push(t);
+                }
+            }
+            sub.start = start;
+            sub.count = indices.len() - start;
+        }
+
+        let removed = n - sources.len();
+        self.reindex_vertices(&sources);
+        self.indices = indices;
+        self.clusters.clear();
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::SubMesh;
+
+    // Dos cuadrados lado a lado, cada uno su parte y con sus propios vértices
+    // en la arista común; el primero además con un vértice repetido.
+    fn two_parts() -> Mesh {
+        let p = |x: f32, y: f32| glm::vec3(x, y, 0.0);
+        let positions = vec![
+            p(0.0, 0.0), p(1.0, 0.0), p(1.0, 1.0), p(0.0, 1.0), p(1.0, 1.0),
+            p(1.0, 0.0), p(2.0, 0.0), p(2.0, 1.0), p(1.0, 1.0),
+        ];
+        let normals = vec![glm::vec3(0.0, 0.0, 1.0); positions.len()];
+        let indices = vec![[0, 1, 2], [0, 4, 3], [5, 6, 7], [5, 7, 8]];
+        let mut mesh = Mesh::new(positions, normals, Vec::new(), indices, 1.0);
+        mesh.submeshes = vec![SubMesh::new("a", 0, 2, None), SubMesh::new("b", 2, 2, None)];
+        mesh
+    }
+
+    fn shared_vertices(mesh: &Mesh) -> usize {
+        let mut owner = vec![None; mesh.positions.len()];
+        let mut shared = 0;
+        for (p, sub) in mesh.submeshes.iter().enumerate() {
+            for &v in mesh.indices[sub.start..sub.start + sub.count].iter().flatten() {
+                match owner[v as usize] {
+                    None => owner[v as usize] = Some(p),
+                    Some(o) if o != p => shared += 1,
+                    _ => {}
+                }
+            }
+        }
+        shared
+    }
+
+    #[test]
+    fn weld_keeps_vertices_per_part() {
+        let mut mesh = two_parts();
+        let removed = mesh.weld_vertices(&WeldOptions::default());
+        assert_eq!(removed, 1, "solo el vértice repetido dentro de la primera parte");
+        assert_eq!(mesh.positions.len(), 8);
+        assert_eq!(shared_vertices(&mesh), 0);
+        assert_eq!(mesh.submeshes[0].count + mesh.submeshes[1].count, 4);
+    }
+}
@@ -0,0 +1,135 @@
+use nalgebra_glm as glm;
+
+use crate::background::XorShift;
+use crate::shader::smoothstep;
+use crate::texture::{Texture, TextureFormat};
+
+// Mapas de normales en espacio tangente (convención OpenGL: R = +u, G = +v,
+// B = normal). Se cargan de imagen o se generan a partir de un campo de alturas.
+
+// Convierte alturas (fila 0 = arriba de la imagen) en un mapa de normales.
+// Repite en ambos ejes; `strength` escala las pendientes.
+pub fn from_heights(width: usize, height: usize, heights: &[f32], strength: f32) -> Texture {
+    let h = |x: isize, y: isize| {
+        let x = x.rem_euclid(width as isize) as usize;
+        let y = y.rem_euclid(height as isize) as usize;
+        heights[y * width + x]
+    };
+    Texture::from_fn(width, height, TextureFormat::Rgb8, |x, y| {
+        let (x, y) = (x as isize, y as isize);
+        let dx = (h(x + 1, y) - h(x - 1, y)) * 0.5;
+        let dy = (h(x, y + 1) - h(x, y - 1)) * 0.5;
+        // v crece hacia arriba: la pendiente en v es la de las filas cambiada de signo
+        let n = glm::normalize(&glm::vec3(-dx * strength, dy * strength, 1.0));
+        [n.x * 0.5 + 0.5, n.y * 0.5 + 0.5, n.z * 0.5 + 0.5, 1.0]
+    })
+}
+
+// Paneles del casco: juntas hundidas entre placas, remaches en los bordes y
+// algunas placas ligeramente elevadas. `panels` placas por lado de la textura.
+pub fn hull_panels(size: usize, panels: usize, seed: u64) -> Texture {
+    let size = size.max(16);
+    let panels = panels.max(1);
+    let mut rng = XorShift::new(seed);
+    let lift: Vec<f32> = (0..panels * panels).map(|_| if rng.next_f32() < 0.3 { 0.35 } else { 0.0 }).collect();
+
+    let cell = size as f32 / panels as f32;
+    let mut heights = vec![0.0f32; size * size];
+    for y in 0..size {
+        for x in 0..size {
+            let fx = (x as f32 + 0.5) / cell;
+            let fy = (y as f32 + 0.5) / cell;
+            let (px, py) = (fx.floor() as usize % panels, fy.floor() as usize % panels);
+            let (tx, ty) = (fx.fract(), fy.fract());
+
+            // Distancia al borde de la placa, en fracción de placa
+            let edge = tx.min(1.0 - tx).min(ty).min(1.0 - ty);
+            let mut hgt = smoothstep(0.0, 0.05, edge) * (1.0 + lift[py * panels + px]);
+
+            // Remaches a lo largo del borde superior e inferior
+            let rivet_t = (tx * 6.0).fract() - 0.5;
+            for band in [ty - 0.09, 0.91 - ty] {
+                let d = (rivet_t * rivet_t / 36.0 + band * band).sqrt();
+                hgt += smoothstep(0.025, 0.0, d) * 0.6;
+            }
+            heights[y * size + x] = hgt * cell * 0.08;
+        }
+    }
+    from_heights(size, size, &heights, 1.0)
+}
+
+// Ruido de valor repetible en x con periodo `period` (celdas enteras)
+fn value_noise(x: f32, y: f32, period: i32, seed: u32) -> f32 {
+    let hash = |ix: i32, iy: i32| {
+        let ix = ix.rem_euclid(period) as u32;
+        let mut h = ix.wrapping_mul(0x27d4_eb2d) ^ (iy as u32).wrapping_mul(0x1656_67b1) ^ seed;
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2c1b_3c6d);
+        h ^= h >> 12;
+        (h & 0xFFFF) as f32 / 65535.0
+    };
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (smoothstep(0.0, 1.0, x - x0), smoothstep(0.0, 1.0, y - y0));
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let top = hash(ix, iy) * (1.0 - tx) + hash(ix + 1, iy) * tx;
+    let bottom = hash(ix, iy + 1) * (1.0 - tx) + hash(ix + 1, iy + 1) * tx;
+    top * (1.0 - ty) + bottom * ty
+}
+
+// Terreno rocoso equirectangular (`width` x `width/2`): cráteres con borde
+// elevado sobre ruido fractal. Las distancias se corrigen por latitud para
+// que los cráteres sean redondos sobre la esfera.
+pub fn rocky_craters(width: usize, craters: usize, seed: u64) -> Texture {
+    let width = width.max(16);
+    let height = width / 2;
+    let mut heights = vec![0.0f32; width * height];
+
+    // Base: ruido fractal de 4 octavas
+    for y in 0..height {
+        for x in 0..width {
+            let mut amp = 1.0;
+            let mut freq = 8.0;
+            let mut acc = 0.0;
+            for octave in 0..4 {
+                let nx = x as f32 / width as f32 * freq;
+                let ny = y as f32 / width as f32 * freq;
+                acc += value_noise(nx, ny, freq as i32, seed as u32 + octave) * amp;
+                amp *= 0.5;
+                freq *= 2.0;
+            }
+            heights[y * width + x] = acc * 3.0;
+        }
+    }
+
+    // Cráteres: muchos pequeños, pocos grandes
+    let mut rng = XorShift::new(seed);
+    for _ in 0..craters {
+        let cu = rng.next_f32();
+        let cv = 0.1 + rng.next_f32() * 0.8;
+        let radius = (0.004 + rng.next_f32().powf(4.0) * 0.05) * width as f32;
+        let depth = radius * 0.35;
+
+        let cx = cu * width as f32;
+        let cy = cv * height as f32;
+        let lat_scale = ((cv - 0.5) * std::f32::consts::PI).cos().max(0.2);
+        let reach_x = (radius * 1.6 / lat_scale) as isize;
+        let reach_y = (radius * 1.6) as isize;
+
+        for oy in -reach_y..=reach_y {
+            let y = cy as isize + oy;
+            if y < 0 || y >= height as isize { continue; }
+            for ox in -reach_x..=reach_x {
+                let x = (cx as isize + ox).rem_euclid(width as isize) as usize;
+                let dx = ox as f32 * lat_scale;
+                let dy = oy as f32;
+                let t = (dx * dx + dy * dy).sqrt() / radius;
+                if t > 1.6 { continue; }
+                let bowl = if t < 1.0 { t * t - 1.0 } else { 0.0 };
+                let rim = 0.3 * (-((t - 1.0) / 0.18).powi(2)).exp();
+                heights[y as usize * width + x] += (bowl + rim) * depth;
+            }
+        }
+    }
+
+    from_heights(width, height, &heights, 1.0)
+}
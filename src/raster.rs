@@ -11,6 +11,7 @@ pub fn put_pixel(buf: &mut [u32], w: usize, h: usize, x: i32, y: i32, color: u32
 }
 
 // Línea Bresenham
+#[allow(dead_code, clippy::too_many_arguments)]
 pub fn line(buf: &mut [u32], w: usize, h: usize, mut x0: i32, mut y0: i32, x1: i32, y1: i32, color: u32) {
     let dx = (x1 - x0).abs();
     let sx = if x0 < x1 { 1 } else { -1 };
@@ -5,13 +5,21 @@ fn edge(ax: f32, ay: f32, bx: f32, by: f32, px: f32, py: f32) -> f32 {
     (px - ax) * (by - ay) - (py - ay) * (bx - ax)
 }
 
+// Modo de mezcla para los fragmentos (pasadas transparentes)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Blend {
+    Opaque,
+    Alpha,     // src * a + dst * (1 - a)
+    Additive,  // dst + src * a
+}
+
 pub fn tri_fill_z(
     color: u32,
     buf: &mut [u32],
     depth: &mut [f32],
     w: usize,
     h: usize,
-    v: [(f32, f32, f32); 3], // (x,y,z) en pantalla, z normalizada [0,1]
+    v: [(f32, f32, f32); 3], // (x,y,z) en pantalla, z = profundidad en espacio vista (menor = más cerca)
 ) {
     let (x0,y0,z0) = v[0];
     let (x1,y1,z1) = v[1];
@@ -51,3 +59,84 @@ pub fn tri_fill_z(
         }
     }
 }
+
+#[inline]
+fn blend_channel(src: u8, dst: u32, a: f32, blend: Blend) -> u32 {
+    let s = src as f32;
+    let d = dst as f32;
+    let out = match blend {
+        Blend::Opaque   => s,
+        Blend::Alpha    => s * a + d * (1.0 - a),
+        Blend::Additive => d + s * a,
+    };
+    out.clamp(0.0, 255.0) as u32
+}
+
+// Variante por fragmento: `frag` recibe las baricéntricas (b0,b1,b2) y devuelve
+// (r,g,b,a) o None si el fragmento se descarta.
+#[allow(clippy::too_many_arguments)]
+pub fn tri_fill_z_frag<F>(
+    buf: &mut [u32],
+    depth: &mut [f32],
+    w: usize,
+    h: usize,
+    v: [(f32, f32, f32); 3],
+    blend: Blend,
+    depth_write: bool,
+    mut frag: F,
+) where
+    F: FnMut(f32, f32, f32) -> Option<(u8, u8, u8, u8)>,
+{
+    let (x0,y0,z0) = v[0];
+    let (x1,y1,z1) = v[1];
+    let (x2,y2,z2) = v[2];
+
+    let min_x = (x0.min(x1).min(x2).floor().max(0.0)) as i32;
+    let min_y = (y0.min(y1).min(y2).floor().max(0.0)) as i32;
+    let max_x = (x0.max(x1).max(x2).ceil().min((w - 1) as f32)) as i32;
+    let max_y = (y0.max(y1).max(y2).ceil().min((h - 1) as f32)) as i32;
+
+    let area = edge(x0,y0,x1,y1,x2,y2);
+    if area == 0.0 { return; }
+    let inv = 1.0 / area;
+
+    for y in min_y..=max_y {
+        let py = y as f32 + 0.5;
+        for x in min_x..=max_x {
+            let px = x as f32 + 0.5;
+
+            let w0 = edge(x1,y1,x2,y2,px,py);
+            let w1 = edge(x2,y2,x0,y0,px,py);
+            let w2 = edge(x0,y0,x1,y1,px,py);
+
+            if !((w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0) || (w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0)) {
+                continue;
+            }
+
+            let b0 = w0 * inv;
+            let b1 = w1 * inv;
+            let b2 = w2 * inv;
+            let z = b0 * z0 + b1 * z1 + b2 * z2;
+
+            let idx = y as usize * w + x as usize;
+            if z >= depth[idx] { continue; }
+
+            let Some((r, g, b, a)) = frag(b0, b1, b2) else { continue; };
+            if a == 0 { continue; }
+            let alpha = a as f32 / 255.0;
+
+            let dst = buf[idx];
+            let color = if blend == Blend::Opaque {
+                (0xFFu32 << 24) | ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+            } else {
+                let dr = blend_channel(r, (dst >> 16) & 0xFF, alpha, blend);
+                let dg = blend_channel(g, (dst >> 8) & 0xFF, alpha, blend);
+                let db = blend_channel(b, dst & 0xFF, alpha, blend);
+                (0xFFu32 << 24) | (dr << 16) | (dg << 8) | db
+            };
+
+            if depth_write { depth[idx] = z; }
+            buf[idx] = color;
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use nalgebra_glm as glm;
+
+use crate::material::Material;
+use crate::mesh::Mesh;
+use crate::mesh_bounds::Bounds;
+use crate::raster::rgb;
+use crate::raster_z::{tri_fill_z, tri_fill_z_frag, Blend};
+use crate::shader::{FragInput, Shader, TriInput, Uniforms};
+use crate::shader_textured::TexturedShader;
+
+// Proyección ortográfica compartida por todas las mallas de un cuadro
+// (así el planeta y sus anillos usan el mismo centro, escala y profundidad).
+#[derive(Clone, Copy)]
+pub struct Projection {
+    pub center: glm::Vec3,
+    pub scale: f32,
+}
+
+impl Projection {
+    #[inline]
+    pub fn project(&self, v: glm::Vec3, width: usize, height: usize) -> (i32, i32) {
+        let hw = (width as f32) * 0.5;
+        let hh = (height as f32) * 0.5;
+        let sx = (v.x - self.center.x) * self.scale + hw;
+        let sy = hh - ((v.y - self.center.y) * self.scale);
+        (sx.round() as i32, sy.round() as i32)
+    }
+
+    // Radio en píxeles de una esfera de radio `radius` (espacio del modelo)
+    // tras `model`: se toma el eje que más escala.
+    pub fn radius_px(&self, radius: f32, model: &glm::Mat4) -> f32 {
+        let m = glm::mat4_to_mat3(model);
+        let axis_scale = (0..3).map(|c| m.column(c).magnitude()).fold(0.0, f32::max);
+        radius * axis_scale * self.scale
+    }
+
+    // ¿Toca la pantalla la esfera envolvente de `bounds` tras `model`?
+    // Ortográfica: basta con el rectángulo en x/y (no hay planos cercano/lejano).
+    pub fn sphere_visible(&self, bounds: &Bounds, model: &glm::Mat4, width: usize, height: usize) -> bool {
+        let c = (model * glm::vec4(bounds.center.x, bounds.center.y, bounds.center.z, 1.0)).xyz();
+        let r = self.radius_px(bounds.radius, model);
+        let sx = (c.x - self.center.x) * self.scale + width as f32 * 0.5;
+        let sy = height as f32 * 0.5 - (c.y - self.center.y) * self.scale;
+        sx + r >= 0.0 && sx - r <= width as f32 && sy + r >= 0.0 && sy - r <= height as f32
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct DrawOptions {
+    pub cull_backfaces: bool,
+    pub blend: Blend,
+    pub depth_write: bool,
+}
+
+impl Default for DrawOptions {
+    fn default() -> Self {
+        Self { cull_backfaces: false, blend: Blend::Opaque, depth_write: true }
+    }
+}
+
+impl DrawOptions {
+    // Pasada transparente: mezcla alfa, sin escribir profundidad, doble cara
+    pub fn transparent() -> Self {
+        Self { cull_backfaces: false, blend: Blend::Alpha, depth_write: false }
+    }
+
+    // Capa emisiva (atmósfera): suma al fondo, solo caras frontales
+    pub fn additive() -> Self {
+        Self { cull_backfaces: true, blend: Blend::Additive, depth_write: false }
+    }
+}
+
+// Contadores del cuadro actual (se reinician con `clear`). Un objeto es
+// cada parte dibujada; los clusters solo cuentan en mallas que los tienen.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RenderStats {
+    pub objects: usize,
+    pub objects_culled: usize,
+    pub clusters: usize,
+    pub clusters_culled: usize,
+    pub triangles: usize,
+}
+
+// Framebuffer + cachés por vértice reutilizadas entre mallas y cuadros.
+// `v_stamp` marca qué vértices ya se transformaron en la parte actual.
+pub struct Renderer {
+    pub width: usize,
+    pub height: usize,
+    pub color: Vec<u32>,
+    pub depth: Vec<f32>,
+    pub stats: RenderStats,
+    v_view:   Vec<glm::Vec3>,
+    v_screen: Vec<(f32, f32)>,
+    v_stamp:  Vec<u32>,
+    stamp:    u32,
+}
+
+impl Renderer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            color: vec![rgb(8,10,14); width * height],
+            depth: vec![f32::INFINITY; width * height],
+            stats: RenderStats::default(),
+            v_view: Vec::new(),
+            v_screen: Vec::new(),
+            v_stamp: Vec::new(),
+            stamp: 0,
+        }
+    }
+
+    pub fn clear(&mut self, color: u32) {
+        self.color.fill(color);
+        self.depth.fill(f32::INFINITY);
+        self.stats = RenderStats::default();
+    }
+
+    // Dibuja todas las partes visibles con el mismo shader
+    pub fn draw_mesh(
+        &mut self,
+        mesh: &Mesh,
+        model: &glm::Mat4,
+        proj: &Projection,
+        shader: &dyn Shader,
+        uniforms: &Uniforms,
+        opts: DrawOptions,
+    ) {
+        for part in 0..mesh.submeshes.len() {
+            self.draw_submesh(mesh, part, model, proj, shader, uniforms, opts);
+        }
+    }
+
+    // Dibuja una parte (si es visible) con su transformación propia
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_submesh(
+        &mut self,
+        mesh: &Mesh,
+        part: usize,
+        model: &glm::Mat4,
+        proj: &Projection,
+        shader: &dyn Shader,
+        uniforms: &Uniforms,
+        opts: DrawOptions,
+    ) {
+        let Some(sub) = mesh.submeshes.get(part) else { return };
+        if !sub.visible || sub.count == 0 { return; }
+        let part_model = model * sub.transform;
+
+        // Fuera de pantalla: ni se transforman sus vértices. Sin límites por
+        // parte (malla editada a mano) se usan los de la malla entera.
+        let bounds = mesh.part_bounds.get(part)
+            .filter(|_| mesh.part_bounds.len() == mesh.submeshes.len())
+            .unwrap_or(&mesh.bounds);
+        if !proj.sphere_visible(bounds, &part_model, self.width, self.height) {
+            self.stats.objects_culled += 1;
+            return;
+        }
+        self.stats.objects += 1;
+        self.begin_part(mesh);
+
+        let end = sub.start + sub.count;
+        if mesh.clusters.is_empty() {
+            let tris = &mesh.indices[sub.start..end];
+            self.stats.triangles += sub.count;
+            self.transform_vertices(mesh, tris, &part_model, proj);
+            self.raster_triangles(mesh, tris, &part_model, shader, uniforms, opts);
+        } else {
+            for c in mesh.clusters.iter().filter(|c| c.start >= sub.start && c.start < end) {
+                if !proj.sphere_visible(&c.bounds, &part_model, self.width, self.height) {
+                    self.stats.clusters_culled += 1;
+                    continue;
+                }
+                // Solo los vértices de los clusters visibles
+                let tris = &mesh.indices[c.start..c.start + c.count];
+                self.stats.clusters += 1;
+                self.stats.triangles += c.count;
+                self.transform_vertices(mesh, tris, &part_model, proj);
+                self.raster_triangles(mesh, tris, &part_model, shader, uniforms, opts);
+            }
+        }
+    }
+
+    // Dibuja cada parte con su material del MTL (TexturedShader).
+    // Las opacas primero; las transparentes después, sin escribir profundidad.
+    pub fn draw_mesh_materials(
+        &mut self,
+        mesh: &Mesh,
+        model: &glm::Mat4,
+        proj: &Projection,
+        uniforms: &Uniforms,
+        opts: DrawOptions,
+    ) {
+        let fallback = Material::default();
+        for transparent_pass in [false, true] {
+            for (part, sub) in mesh.submeshes.iter().enumerate() {
+                let material = sub.material.and_then(|i| mesh.materials.get(i)).unwrap_or(&fallback);
+                if material.is_transparent() != transparent_pass { continue; }
+                let pass_opts = if transparent_pass {
+                    DrawOptions { cull_backfaces: opts.cull_backfaces, ..DrawOptions::transparent() }
+                } else {
+                    opts
+                };
+                self.draw_submesh(mesh, part, model, proj, &TexturedShader { material }, uniforms, pass_opts);
+            }
+        }
+    }
+
+    // Nueva parte: ningún vértice transformado todavía
+    fn begin_part(&mut self, mesh: &Mesh) {
+        let n = mesh.positions.len();
+        if self.v_view.len() < n {
+            self.v_view.resize(n, glm::vec3(0.0, 0.0, 0.0));
+            self.v_screen.resize(n, (0.0, 0.0));
+            self.v_stamp.resize(n, 0);
+        }
+        self.stamp = self.stamp.wrapping_add(1);
+        if self.stamp == 0 {
+            self.v_stamp.fill(0);
+            self.stamp = 1;
+        }
+    }
+
+    // PASS 1 y 2: vértices de `tris` a espacio vista y a pantalla (una vez
+    // por vértice y parte, aunque lo compartan varios clusters)
+    fn transform_vertices(&mut self, mesh: &Mesh, tris: &[[u32; 3]], model: &glm::Mat4, proj: &Projection) {
+        for &i in tris.iter().flatten() {
+            let i = i as usize;
+            if self.v_stamp[i] == self.stamp { continue; }
+            self.v_stamp[i] = self.stamp;
+            let v = mesh.positions[i];
+            self.v_view[i] = (model * glm::vec4(v.x, v.y, v.z, 1.0)).xyz();
+            let s = proj.project(self.v_view[i], self.width, self.height);
+            self.v_screen[i] = (s.0 as f32, s.1 as f32);
+        }
+    }
+
+    // PASS 3: raster de `tris` con los vértices ya transformados
+    fn raster_triangles(
+        &mut self,
+        mesh: &Mesh,
+        tris: &[[u32; 3]],
+        model: &glm::Mat4,
+        shader: &dyn Shader,
+        uniforms: &Uniforms,
+        opts: DrawOptions,
+    ) {
+        let per_fragment = shader.per_fragment(uniforms);
+
+        for f in tris {
+            let i0 = f[0] as usize;
+            let i1 = f[1] as usize;
+            let i2 = f[2] as usize;
+
+            let q0 = self.v_view[i0];
+            let q1 = self.v_view[i1];
+            let q2 = self.v_view[i2];
+
+            if opts.cull_backfaces {
+                let nrm = (q1 - q0).cross(&(q2 - q0));
+                if nrm.z >= 0.0 { continue; } // cámara mira -Z en este "espacio vista"
+            }
+
+            // Normales transformadas por el modelo (sin traslación)
+            let n0 = (model * glm::vec4(mesh.normals[i0].x, mesh.normals[i0].y, mesh.normals[i0].z, 0.0)).xyz().normalize();
+            let n1 = (model * glm::vec4(mesh.normals[i1].x, mesh.normals[i1].y, mesh.normals[i1].z, 0.0)).xyz().normalize();
+            let n2 = (model * glm::vec4(mesh.normals[i2].x, mesh.normals[i2].y, mesh.normals[i2].z, 0.0)).xyz().normalize();
+
+            // UVs y tangentes (rotadas como las normales; w conserva el signo)
+            let uv_at = |i: usize| mesh.uvs.get(i).copied().unwrap_or_else(glm::Vec2::zeros);
+            let tangent_at = |i: usize| match mesh.tangents.get(i) {
+                Some(t) => {
+                    let tv = (model * glm::vec4(t.x, t.y, t.z, 0.0)).xyz();
+                    glm::vec4(tv.x, tv.y, tv.z, t.w)
+                }
+                None => glm::Vec4::zeros(),
+            };
+
+            let color_at = |i: usize| mesh.colors.get(i).copied().unwrap_or_else(|| glm::vec3(1.0, 1.0, 1.0));
+
+            let tri_in = TriInput {
+                p0: q0, p1: q1, p2: q2, n0, n1, n2,
+                uv0: uv_at(i0), uv1: uv_at(i1), uv2: uv_at(i2),
+                t0: tangent_at(i0), t1: tangent_at(i1), t2: tangent_at(i2),
+                c0: color_at(i0), c1: color_at(i1), c2: color_at(i2),
+            };
+
+            let s0 = self.v_screen[i0];
+            let s1 = self.v_screen[i1];
+            let s2 = self.v_screen[i2];
+
+            let v0 = (s0.0, s0.1, q0.z);
+            let v1 = (s1.0, s1.1, q1.z);
+            let v2 = (s2.0, s2.1, q2.z);
+
+            if per_fragment {
+                // Derivadas por píxel de UVs y posición (lineales en pantalla)
+                let (gx, gy) = barycentric_gradients(s0, s1, s2);
+                let uv_dx = tri_in.uv0 * gx[0] + tri_in.uv1 * gx[1] + tri_in.uv2 * gx[2];
+                let uv_dy = tri_in.uv0 * gy[0] + tri_in.uv1 * gy[1] + tri_in.uv2 * gy[2];
+                let pos_dx = q0 * gx[0] + q1 * gx[1] + q2 * gx[2];
+                let pos_dy = q0 * gy[0] + q1 * gy[1] + q2 * gy[2];
+                tri_fill_z_frag(
+                    &mut self.color, &mut self.depth, self.width, self.height,
+                    [v0, v1, v2], opts.blend, opts.depth_write,
+                    |b0, b1, b2| {
+                        let normal = (n0 * b0 + n1 * b1 + n2 * b2).normalize();
+                        let t = tri_in.t0 * b0 + tri_in.t1 * b1 + tri_in.t2 * b2;
+                        // Gram-Schmidt: tangente perpendicular a la normal interpolada
+                        let t3 = t.xyz() - normal * normal.dot(&t.xyz());
+                        let (tangent, bitangent) = if t3.magnitude() > 1e-9 {
+                            let tangent = t3.normalize();
+                            let sign = if t.w < 0.0 { -1.0 } else { 1.0 };
+                            (tangent, normal.cross(&tangent) * sign)
+                        } else {
+                            (glm::Vec3::zeros(), glm::Vec3::zeros())
+                        };
+                        let frag = FragInput {
+                            pos: q0 * b0 + q1 * b1 + q2 * b2,
+                            normal,
+                            uv: tri_in.uv0 * b0 + tri_in.uv1 * b1 + tri_in.uv2 * b2,
+                            tangent,
+                            bitangent,
+                            color: tri_in.c0 * b0 + tri_in.c1 * b1 + tri_in.c2 * b2,
+                            uv_dx,
+                            uv_dy,
+                            pos_dx,
+                            pos_dy,
+                        };
+                        Some(shader.shade_fragment(uniforms, &tri_in, &frag))
+                    },
+                );
+            } else {
+                let (r,g,b) = shader.shade(uniforms, &tri_in);
+                tri_fill_z(rgb(r,g,b), &mut self.color, &mut self.depth, self.width, self.height, [v0, v1, v2]);
+            }
+        }
+    }
+}
+
+// Derivadas de las coordenadas baricéntricas respecto a x e y de pantalla
+// (las mismas que calcula `tri_fill_z_frag` con sus funciones de arista)
+fn barycentric_gradients(s0: (f32, f32), s1: (f32, f32), s2: (f32, f32)) -> ([f32; 3], [f32; 3]) {
+    let area = (s2.0 - s0.0) * (s1.1 - s0.1) - (s2.1 - s0.1) * (s1.0 - s0.0);
+    if area.abs() <= 1e-12 {
+        return ([0.0; 3], [0.0; 3]);
+    }
+    let inv = 1.0 / area;
+    (
+        [(s2.1 - s1.1) * inv, (s0.1 - s2.1) * inv, (s1.1 - s0.1) * inv],
+        [(s1.0 - s2.0) * inv, (s2.0 - s0.0) * inv, (s0.0 - s1.0) * inv],
+    )
+}
@@ -0,0 +1,357 @@
+use nalgebra_glm as glm;
+use serde::Deserialize;
+
+use crate::data_file;
+use crate::shader_atmosphere::AtmosphereParams;
+use crate::shader_params::ShaderParams;
+use crate::shader_pbr::PbrMaterial;
+
+// Descripción de la escena: qué cuerpos hay, con qué malla y shader, dónde
+// están y cómo se mueven, más la luz y la cámara. Se lee con `data_file`
+// (RON, JSON o TOML). Ángulos en grados; distancias en unidades del mundo.
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, clap::ValueEnum)]
+pub enum ShaderKind {
+    Metal,     // Metal procedural (paneles, rayones, reflejos del entorno)
+    Sun,
+    Rocky,
+    GasGiant,
+    Pbr,       // Cook-Torrance con el material del cuerpo (el del casco del OVNI si no tiene)
+    Materials, // Cada parte con su material del archivo (MTL / glTF)
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum MeshSource {
+    Sphere,       // Esfera de los planetas (LOD, `assets/sphere.obj` y formas de la tecla T)
+    File(String), // .obj, .gltf, .glb, .stl o .ply
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum NormalMapSource {
+    Panels,       // Paneles y remaches (assets/ufo_normal.png o generado)
+    Craters,      // Cráteres (assets/rock_normal.png o generado)
+    File(String),
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum AtmosphereKind {
+    Rocky,
+    GasGiant,
+}
+
+// Atmósfera de un cuerpo: un preset por su nombre o los parámetros completos
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Atmosphere {
+    Preset(AtmosphereKind),
+    Custom(AtmosphereParams),
+}
+
+impl Atmosphere {
+    pub fn params(self) -> AtmosphereParams {
+        match self {
+            Atmosphere::Preset(AtmosphereKind::Rocky) => AtmosphereParams::rocky(),
+            Atmosphere::Preset(AtmosphereKind::GasGiant) => AtmosphereParams::gas_giant(),
+            Atmosphere::Custom(params) => params,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum MaterialKind {
+    UfoHull,
+    UfoDome,
+}
+
+// Material del shader `Pbr`: un preset por su nombre o el material completo
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PbrSource {
+    Preset(MaterialKind),
+    Custom(PbrMaterial),
+}
+
+impl PbrSource {
+    pub fn material(self) -> PbrMaterial {
+        match self {
+            PbrSource::Preset(MaterialKind::UfoHull) => PbrMaterial::ufo_hull(),
+            PbrSource::Preset(MaterialKind::UfoDome) => PbrMaterial::ufo_dome(),
+            PbrSource::Custom(material) => material,
+        }
+    }
+}
+
+// Anillos en radios planetarios, inclinados `tilt` grados sobre el ecuador
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rings {
+    pub inner: f32,
+    pub outer: f32,
+    pub tilt: f32,
+}
+
+impl Default for Rings {
+    fn default() -> Self {
+        Self { inner: 1.25, outer: 2.25, tilt: 25.8 }
+    }
+}
+
+// Órbita circular alrededor de otro cuerpo (declarado antes) o del origen
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Orbit {
+    pub around: Option<String>,
+    pub radius: f32,
+    pub period: f32,      // Segundos por vuelta (negativo: sentido horario)
+    pub phase: f32,       // Ángulo inicial
+    pub inclination: f32, // Giro del plano de la órbita sobre el eje X
+}
+
+impl Default for Orbit {
+    fn default() -> Self {
+        Self { around: None, radius: 0.0, period: 60.0, phase: 0.0, inclination: 0.0 }
+    }
+}
+
+impl Orbit {
+    pub fn offset(&self, time: f32) -> glm::Vec3 {
+        let angle = (self.phase + 360.0 * time / self.period).to_radians();
+        let flat = glm::vec3(angle.cos(), 0.0, -angle.sin()) * self.radius;
+        glm::rotate_x_vec3(&flat, self.inclination.to_radians())
+    }
+}
+
+fn default_color() -> [u8; 3] { [80, 100, 140] }
+fn default_scale() -> [f32; 3] { [1.0, 1.0, 1.0] }
+fn default_size() -> f32 { 1.0 }
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Body {
+    #[serde(default)]
+    pub name: String,
+    pub mesh: MeshSource,
+    pub shader: ShaderKind,
+    #[serde(default)]
+    pub params: Option<ShaderParams>,  // En lugar del archivo de parámetros de la escena
+    #[serde(default = "default_color")]
+    pub color: [u8; 3],                // Tinte del metal
+    #[serde(default)]
+    pub position: [f32; 3],            // Centro (o desplazamiento sobre la órbita)
+    #[serde(default)]
+    pub rotation: [f32; 3],            // Euler X, Y, Z
+    #[serde(default = "default_scale")]
+    pub scale: [f32; 3],
+    #[serde(default = "default_size")]
+    pub size: f32,                     // Mayor dimensión de la malla antes de `scale`
+    #[serde(default)]
+    pub spin: f32,                     // Grados por segundo alrededor de su eje Y
+    #[serde(default)]
+    pub orbit: Option<Orbit>,
+    #[serde(default)]
+    pub normal_map: Option<NormalMapSource>,
+    #[serde(default)]
+    pub atmosphere: Option<Atmosphere>,
+    #[serde(default)]
+    pub material: Option<PbrSource>,   // Del shader `Pbr`
+    #[serde(default)]
+    pub rings: Option<Rings>,
+}
+
+impl Body {
+    // Orientación fija (sin el giro propio), para los anillos
+    pub fn tilt(&self) -> glm::Mat4 {
+        let [x, y, z] = self.rotation.map(f32::to_radians);
+        glm::rotation(z, &glm::vec3(0.0, 0.0, 1.0))
+            * glm::rotation(y, &glm::vec3(0.0, 1.0, 0.0))
+            * glm::rotation(x, &glm::vec3(1.0, 0.0, 0.0))
+    }
+
+    pub fn orientation(&self, time: f32) -> glm::Mat4 {
+        self.tilt() * glm::rotation((self.spin * time).to_radians(), &glm::vec3(0.0, 1.0, 0.0))
+    }
+
+    pub fn scaling(&self) -> glm::Mat4 {
+        glm::scaling(&glm::make_vec3(&self.scale))
+    }
+
+    pub fn pbr_material(&self) -> PbrMaterial {
+        self.material.map_or_else(PbrMaterial::ufo_hull, PbrSource::material)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum Light {
+    Directional { direction: [f32; 3] }, // Fija respecto a la cámara, como un foco de estudio
+    Point { position: [f32; 3] },        // En el mundo (p.ej. el sol de un sistema)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Camera {
+    pub focus: Option<String>, // Cuerpo en el centro de la pantalla (el primero si falta)
+    pub solo: bool,            // Dibujar solo el cuerpo enfocado
+    pub view_size: f32,        // Unidades del mundo que caben en el lado menor de la ventana
+    pub yaw: f32,
+    pub pitch: f32,
+    pub zoom: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self { focus: None, solo: false, view_size: 2.08, yaw: 0.0, pitch: 0.0, zoom: 1.0 }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scene {
+    pub background: [u8; 3],
+    pub ambient: f32,
+    pub light: Light,
+    pub shader_params: Option<String>, // Archivo de `ShaderParams` (se recarga en caliente)
+    pub camera: Camera,
+    pub bodies: Vec<Body>,
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self {
+            background: [8, 10, 14],
+            ambient: 0.20,
+            light: Light::Directional { direction: [-0.4, 0.8, 0.35] },
+            shader_params: None,
+            camera: Camera::default(),
+            bodies: Vec::new(),
+        }
+    }
+}
+
+impl Scene {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let scene: Self = data_file::load(path)?;
+        scene.validate().map_err(|e| format!("Escena '{}': {}", path, e))?;
+        Ok(scene)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.bodies.is_empty() {
+            return Err("no hay cuerpos".to_string());
+        }
+        for (i, body) in self.bodies.iter().enumerate() {
+            if !body.name.is_empty() && self.body_index(&body.name) != Some(i) {
+                return Err(format!("nombre repetido: '{}'", body.name));
+            }
+            if body.size <= 0.0 || body.scale.contains(&0.0) {
+                return Err(format!("'{}': tamaño o escala nulos", body.name));
+            }
+            let m = body.pbr_material();
+            if !(0.0..=1.0).contains(&m.metallic) || !(0.04..=1.0).contains(&m.roughness) {
+                return Err(format!("'{}': metalicidad fuera de [0,1] o rugosidad fuera de [0.04,1]", body.name));
+            }
+            if let Some(atmosphere) = body.atmosphere && atmosphere.params().radius <= 1.0 {
+                return Err(format!("'{}': la atmósfera debe ser mayor que el planeta (radio > 1)", body.name));
+            }
+            let Some(orbit) = &body.orbit else { continue };
+            if orbit.period == 0.0 {
+                return Err(format!("'{}': periodo de órbita nulo", body.name));
+            }
+            if let Some(parent) = &orbit.around {
+                match self.body_index(parent) {
+                    Some(p) if p < i => {}
+                    Some(_) => return Err(format!("'{}' orbita a '{}', que debe declararse antes", body.name, parent)),
+                    None => return Err(format!("'{}' orbita a '{}', que no existe", body.name, parent)),
+                }
+            }
+        }
+        if let Some(focus) = &self.camera.focus {
+            self.body_index(focus).ok_or_else(|| format!("la cámara enfoca a '{}', que no existe", focus))?;
+        }
+        Ok(())
+    }
+
+    pub fn body_index(&self, name: &str) -> Option<usize> {
+        self.bodies.iter().position(|b| b.name == name)
+    }
+
+    pub fn focus(&self) -> usize {
+        self.camera.focus.as_deref().and_then(|f| self.body_index(f)).unwrap_or(0)
+    }
+
+    // Posición de cada cuerpo en el instante `time` (los padres van antes)
+    pub fn positions(&self, time: f32) -> Vec<glm::Vec3> {
+        let mut out: Vec<glm::Vec3> = Vec::with_capacity(self.bodies.len());
+        for body in &self.bodies {
+            let mut p = glm::make_vec3(&body.position);
+            if let Some(orbit) = &body.orbit {
+                let parent = orbit.around.as_deref().and_then(|n| self.body_index(n));
+                p += parent.map_or(glm::Vec3::zeros(), |i| out[i]) + orbit.offset(time);
+            }
+            out.push(p);
+        }
+        out
+    }
+
+    // Sustituye las mallas de archivo (p.ej. por un modelo pasado en la línea de órdenes)
+    pub fn replace_models(&mut self, path: &str) {
+        for body in &mut self.bodies {
+            if let MeshSource::File(p) = &mut body.mesh {
+                *p = path.to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Como los archivos de escena: `Some(...)` implícito
+    fn ron<T: serde::de::DeserializeOwned>(text: &str) -> Result<T, ron::error::SpannedError> {
+        ron::from_str(&format!("#![enable(implicit_some)]\n{}", text))
+    }
+
+    #[test]
+    fn presets_by_name() {
+        let body: Body = ron("(mesh: Sphere, shader: Pbr, atmosphere: GasGiant, material: UfoDome)").unwrap();
+        assert_eq!(body.atmosphere.map(|a| a.params().radius), Some(AtmosphereParams::gas_giant().radius));
+        assert_eq!(body.pbr_material().roughness, PbrMaterial::ufo_dome().roughness);
+    }
+
+    #[test]
+    fn inline_params_ron() {
+        let body: Body = ron(
+            "(mesh: Sphere, shader: Pbr,
+              atmosphere: (radius: 1.2, rayleigh_coeff: [1.0, 2.0, 3.0]),
+              material: (base_color: [0.9, 0.6, 0.2], metallic: 1.0, roughness: 0.5))",
+        ).unwrap();
+        let atmosphere = body.atmosphere.unwrap().params();
+        assert_eq!(atmosphere.radius, 1.2);
+        assert_eq!(atmosphere.rayleigh_coeff, glm::vec3(1.0, 2.0, 3.0));
+        assert_eq!(atmosphere.mie_g, AtmosphereParams::rocky().mie_g, "los campos que faltan salen del preset");
+        let material = body.pbr_material();
+        assert_eq!(material.base_color, glm::vec3(0.9, 0.6, 0.2));
+        assert_eq!((material.metallic, material.roughness), (1.0, 0.5));
+    }
+
+    #[test]
+    fn inline_params_json_and_toml() {
+        let json: Body = serde_json::from_str(
+            r#"{"mesh": "Sphere", "shader": "Rocky", "atmosphere": {"radius": 1.1}, "material": "UfoHull"}"#,
+        ).unwrap();
+        assert_eq!(json.atmosphere.map(|a| a.params().radius), Some(1.1));
+        let toml: Body = toml::from_str(
+            "mesh = \"Sphere\"\nshader = \"Pbr\"\natmosphere = \"Rocky\"\n[material]\nmetallic = 0.2\n",
+        ).unwrap();
+        assert_eq!(toml.pbr_material().metallic, 0.2);
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_bad_ranges() {
+        assert!(ron::<Body>("(mesh: Sphere, shader: Pbr, material: (metalic: 1.0))").is_err());
+        let scene: Scene = ron("(bodies: [(mesh: Sphere, shader: Pbr, material: (roughness: 0.0))])").unwrap();
+        assert!(scene.validate().is_err());
+        let scene: Scene = ron("(bodies: [(mesh: Sphere, shader: Rocky, atmosphere: (radius: 0.9))])").unwrap();
+        assert!(scene.validate().is_err());
+    }
+}
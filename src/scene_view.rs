@@ -0,0 +1,571 @@
+use nalgebra_glm as glm;
+use std::path::Path;
+
+use crate::background::{Background, SkyImage};
+use crate::environment::Environment;
+use crate::mesh::{LoadOptions, Mesh};
+use crate::mesh_lod::MeshLod;
+use crate::mesh_normals::NormalMode;
+use crate::mesh_primitives;
+use crate::mesh_weld::WeldOptions;
+use crate::normal_map;
+use crate::raster::rgb;
+use crate::render::{DrawOptions, Projection, Renderer};
+use crate::scene::{Body, Light, MeshSource, NormalMapSource, Scene, ShaderKind};
+use crate::shader::{FlowmapTexture, GasGiantShader, MetalLambert, RockyPlanetShader, Shader, SunShader, Uniforms};
+use crate::shader_atmosphere::AtmosphereShader;
+use crate::shader_params::ShaderParams;
+use crate::shader_pbr::{PbrMaterial, PbrShader};
+use crate::shader_ring::{RingShader, RingSystem};
+use crate::texture::{Sampler, Texture};
+
+// Archivos que se recargan al cambiar mientras la ventana está abierta
+pub const FLOWMAP_PATH: &str = "Jupiter.png";
+const SPHERE_PATH: &str = "assets/sphere.obj";
+
+// Parte de los modelos que es la cúpula (objeto del OBJ del OVNI)
+const UFO_DOME: &str = "Sphere.001";
+
+// Por encima de este número de triángulos el modelo se simplifica al cargar
+const MAX_MODEL_TRIANGLES: usize = 100_000;
+
+// Triángulos por cluster para descartar zonas fuera de pantalla
+const CLUSTER_TRIANGLES: usize = 64;
+
+// Modelos escaneados muy pesados: se reducen para que el rasterizador aguante
+fn prepare_model(mesh: &mut Mesh) {
+    let before = mesh.indices.len();
+    if before > MAX_MODEL_TRIANGLES {
+        mesh.simplify(MAX_MODEL_TRIANGLES);
+        println!("Modelo simplificado: {} → {} triángulos", before, mesh.indices.len());
+    }
+    mesh.build_clusters(CLUSTER_TRIANGLES);
+}
+
+// Carga un modelo de la escena con `opts`, avisa si la malla tiene problemas y la prepara
+fn load_model(path: &str, opts: &LoadOptions) -> Result<Mesh, String> {
+    let mut mesh = Mesh::load(path, 1.0, opts)?;
+    let report = mesh.validate();
+    if !report.is_clean() {
+        println!("⚠ {} tiene problemas (ver `info --check`):\n{}", path, report);
+    }
+    prepare_model(&mut mesh);
+    Ok(mesh)
+}
+
+// Esfera de los planetas: la de assets/ si existe, si no una generada
+fn load_sphere() -> Result<Mesh, String> {
+    let mut sphere = if Path::new(SPHERE_PATH).exists() {
+        println!("Cargando {}...", SPHERE_PATH);
+        let mut sphere = Mesh::load_obj(SPHERE_PATH, 1.0)?;
+        if sphere.uvs.is_empty() {
+            // Sin UVs en el archivo: proyección esférica para el mapa de normales
+            sphere.compute_spherical_uvs();
+            sphere.compute_tangents()?;
+        }
+        sphere
+    } else {
+        println!("{} no encontrado: esfera UV generada", SPHERE_PATH);
+        mesh_primitives::uv_sphere(1.0, 96, 48)
+    };
+    sphere.fit_to(1.0);
+    Ok(sphere)
+}
+
+// La esfera cargada por niveles, simplificando la original
+fn sphere_levels(sphere: Mesh) -> MeshLod {
+    let tris = sphere.indices.len();
+    let mut lod = MeshLod::new(sphere);
+    lod.push_simplified(tris / 3, 90.0);
+    lod.push_simplified(tris / 10, 35.0);
+    for level in &mut lod.levels {
+        level.mesh.build_clusters(CLUSTER_TRIANGLES);
+    }
+    lod
+}
+
+// Mapa de normales: imagen en assets/ si existe, si no se genera
+fn load_normal_map(source: &NormalMapSource) -> Result<Texture, String> {
+    let (path, generate): (&str, &dyn Fn() -> Texture) = match source {
+        NormalMapSource::Panels => ("assets/ufo_normal.png", &|| normal_map::hull_panels(512, 8, 0x0f0_a11)),
+        NormalMapSource::Craters => ("assets/rock_normal.png", &|| normal_map::rocky_craters(1024, 220, 0x0c4a_7e45)),
+        NormalMapSource::File(path) => return Texture::load(path),
+    };
+    if Path::new(path).exists() {
+        match Texture::load(path) {
+            Ok(tex) => return Ok(tex),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    Ok(generate())
+}
+
+// Centro, mayor dimensión y radio de una malla: el marco con el que se
+// coloca en la escena (un cuerpo de tamaño 1 mide 1 en su mayor dimensión)
+fn mesh_frame(mesh: &Mesh) -> (glm::Vec3, f32, f32) {
+    let size = mesh.bounds.max - mesh.bounds.min;
+    (mesh.bounds.center, size.x.max(size.y).max(size.z).max(1e-6), mesh.bounds.radius)
+}
+
+// Lo que cada cuerpo necesita además de su descripción
+struct BodyState {
+    model: Option<usize>, // Índice en los modelos cargados (None: esfera de los planetas)
+    lod: Option<MeshLod>, // Esfera cargada por niveles (solo planetas)
+    rings: Option<Mesh>,
+}
+
+// Colocación de un cuerpo en el cuadro actual (espacio vista)
+struct Placement {
+    body: usize,
+    model: glm::Mat4,
+    ring_model: glm::Mat4,  // Sin giro propio, inclinado como los anillos
+    origin: glm::Vec3,      // Origen de la malla
+    mesh_center: glm::Vec3, // Centro del marco, en unidades de la malla
+    center: glm::Vec3,
+    radius: f32,
+    scale: f32,             // Unidades de vista por unidad de la malla
+    light_dir: glm::Vec3,
+}
+
+// Lo que el usuario cambia mientras mira la escena (teclado en `view`)
+pub struct ViewState {
+    pub focus: usize,         // Cuerpo en el centro de la pantalla
+    pub yaw: f32,             // Radianes
+    pub pitch: f32,
+    pub zoom: f32,
+    pub squash: bool,         // Achatado del cuerpo enfocado (se hornea al exportar)
+    pub cull_backfaces: bool,
+    pub rings: bool,
+    pub atmosphere: bool,
+    pub normal_maps: bool,
+    pub dome_visible: bool,
+    pub dome_lifted: bool,
+    pub planet_shape: usize,  // 0 = esfera cargada (por niveles), luego `planet_shapes`
+}
+
+impl ViewState {
+    pub fn new(scene: &Scene) -> Self {
+        Self {
+            focus: scene.focus(),
+            yaw: scene.camera.yaw.to_radians(),
+            pitch: scene.camera.pitch.to_radians(),
+            zoom: scene.camera.zoom,
+            squash: false,
+            cull_backfaces: false,
+            rings: false,
+            atmosphere: true,
+            normal_maps: true,
+            dome_visible: true,
+            dome_lifted: false,
+            planet_shape: 0,
+        }
+    }
+
+    // Cámara de la escena (tecla R)
+    pub fn reset_camera(&mut self, scene: &Scene) {
+        self.yaw = scene.camera.yaw.to_radians();
+        self.pitch = scene.camera.pitch.to_radians();
+        self.zoom = scene.camera.zoom;
+        self.squash = false;
+    }
+
+    pub fn camera(&self) -> glm::Mat4 {
+        glm::rotation(self.yaw, &glm::vec3(0.0, 1.0, 0.0)) * glm::rotation(self.pitch, &glm::vec3(1.0, 0.0, 0.0))
+    }
+
+    pub fn squash_matrix(&self) -> glm::Mat4 {
+        if self.squash { glm::scaling(&glm::vec3(1.10, 0.75, 1.10)) } else { glm::identity() }
+    }
+}
+
+// La escena con todo lo que se cargó para dibujarla
+pub struct SceneView {
+    pub scene: Scene,
+    pub model_opts: LoadOptions,
+    models: Vec<(String, Mesh)>,
+    bodies: Vec<BodyState>,
+    pub planet_shapes: Vec<(&'static str, Mesh)>,
+    sphere_frame: (glm::Vec3, f32, f32),
+    normal_maps: Vec<(NormalMapSource, Texture)>,
+    flowmap: Option<FlowmapTexture>,
+    background: Background,
+    environment: Environment,
+    shader_params: ShaderParams,
+    pub pbr_shaders: Vec<PbrShader>, // Uno por cuerpo, con su material
+    dome_shader: PbrShader,
+    pub roughness: f32,       // Desenfoque de los reflejos del metal
+}
+
+impl SceneView {
+    pub fn load(scene: Scene) -> Result<Self, String> {
+        // Cargar flowmap de Jupiter
+        let flowmap = FlowmapTexture::load(FLOWMAP_PATH)?;
+
+        // Mapas de normales que usa la escena. Anisotrópicos: el relieve se ve
+        // de canto hacia el borde de los planetas y del casco
+        let mut normal_maps: Vec<(NormalMapSource, Texture)> = Vec::new();
+        for source in scene.bodies.iter().filter_map(|b| b.normal_map.as_ref()) {
+            if normal_maps.iter().any(|(s, _)| s == source) {
+                continue;
+            }
+            match load_normal_map(source) {
+                Ok(tex) => normal_maps.push((source.clone(), tex.with_sampler(Sampler::anisotropic(8)))),
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+
+        // Modelos de archivo, uno por ruta aunque los usen varios cuerpos.
+        // Normales regeneradas con pliegue: casco suave, borde y cúpula nítidos
+        let model_opts = LoadOptions {
+            keep_file_normals: false,
+            normals: NormalMode::creased_degrees(40.0),
+            weld: Some(WeldOptions::default()),
+        };
+        let mut models: Vec<(String, Mesh)> = Vec::new();
+        for body in &scene.bodies {
+            let MeshSource::File(path) = &body.mesh else { continue };
+            if models.iter().any(|(p, _)| p == path) {
+                continue;
+            }
+            println!("Cargando {}...", path);
+            let mesh = load_model(path, &model_opts)?;
+            println!("✓ {} cargado ({})", path, mesh.summary());
+            for sub in &mesh.submeshes {
+                let material = sub.material.map_or("-", |i| mesh.materials[i].name.as_str());
+                println!("  parte '{}': {} triángulos, material '{}'", sub.name, sub.count, material);
+            }
+            models.push((path.clone(), mesh));
+        }
+
+        let mesh_sphere = load_sphere()?;
+        println!("✓ Esfera lista ({})", mesh_sphere.summary());
+
+        // Marco común de las formas de los planetas: el de la esfera cargada
+        let planet_radius = mesh_sphere.radius();
+        let sphere_frame = mesh_frame(&mesh_sphere);
+
+        // Formas generadas para los planetas (tecla T), en el mismo marco que la esfera
+        let place = |mut m: Mesh| {
+            for p in &mut m.positions { *p += mesh_sphere.center; }
+            m.center = mesh_sphere.center;
+            m.scale = mesh_sphere.scale;
+            m.compute_bounds();
+            m.build_clusters(CLUSTER_TRIANGLES);
+            m
+        };
+        let planet_shapes = vec![
+            ("esfera UV", place(mesh_primitives::uv_sphere(planet_radius, 64, 32))),
+            ("icosfera", place(mesh_primitives::icosphere(planet_radius, 4))),
+            ("cubo-esfera", place(mesh_primitives::cube_sphere(planet_radius, 20))),
+            ("toro", place(mesh_primitives::torus(planet_radius * 0.75, planet_radius * 0.3, 96, 32))),
+            ("disco", place(mesh_primitives::disc(planet_radius, 64, 8))),
+        ];
+
+        // La esfera cargada por niveles de detalle según su radio en pantalla (forma por defecto)
+        let sphere_lod = sphere_levels(mesh_sphere);
+
+        // Cada cuerpo con su modelo, sus niveles de detalle (cada uno elige el
+        // suyo según su tamaño en pantalla) y sus anillos
+        let bodies = scene.bodies.iter().map(|body| {
+            let model = match &body.mesh {
+                MeshSource::File(path) => models.iter().position(|(p, _)| p == path),
+                MeshSource::Sphere => None,
+            };
+            let (center, _, radius) = model.map_or(sphere_frame, |i| mesh_frame(&models[i].1));
+            BodyState {
+                model,
+                lod: model.is_none().then(|| sphere_lod.clone()),
+                rings: body.rings.map(|r| mesh_primitives::ring(center, r.inner * radius, r.outer * radius, 160, 4)),
+            }
+        }).collect();
+
+        // Fondo: estrellas procedurales + cielo opcional (equirect o cubemap)
+        let [r, g, b] = scene.background;
+        let mut background = Background::new((r, g, b));
+        let sky = if Path::new("assets/sky.png").exists() {
+            Some(SkyImage::load_equirect("assets/sky.png"))
+        } else if Path::new("assets/sky").is_dir() {
+            Some(SkyImage::load_cubemap("assets/sky"))
+        } else {
+            None
+        };
+        match sky {
+            Some(Ok(img)) => { background.sky = Some(img); println!("✓ Cielo cargado"); }
+            Some(Err(e)) => eprintln!("Cielo no disponible: {}", e),
+            None => {}
+        }
+
+        // Entorno prefiltrado para los reflejos metálicos (mismo cielo que el fondo)
+        let environment = Environment::from_background(&background, 512, 6);
+
+        // Parámetros de los shaders procedurales (por defecto si la escena no da archivo)
+        let shader_params = match &scene.shader_params {
+            Some(path) => ShaderParams::load(path).unwrap_or_else(|e| {
+                eprintln!("{} (se usan los valores por defecto)", e);
+                ShaderParams::default()
+            }),
+            None => ShaderParams::default(),
+        };
+
+        let pbr_shaders = scene.bodies.iter().map(|b| PbrShader { material: b.pbr_material() }).collect();
+
+        Ok(Self {
+            scene,
+            model_opts,
+            models,
+            bodies,
+            planet_shapes,
+            sphere_frame,
+            normal_maps,
+            flowmap: Some(flowmap),
+            background,
+            environment,
+            shader_params,
+            pbr_shaders,
+            dome_shader: PbrShader { material: PbrMaterial::ufo_dome() },
+            roughness: 0.35,
+        })
+    }
+
+    // Archivos que conviene vigilar para recargarlos en caliente
+    pub fn watched_files(&self) -> Vec<String> {
+        let mut files = vec![FLOWMAP_PATH.to_string(), SPHERE_PATH.to_string()];
+        files.extend(self.models.iter().map(|(p, _)| p.clone()));
+        files.extend(self.scene.shader_params.clone());
+        files
+    }
+
+    // Recarga `path` si es uno de los archivos de la escena. Devuelve el
+    // mensaje para la consola; con errores se conserva la versión anterior.
+    pub fn reload(&mut self, path: &Path) -> Option<Result<String, String>> {
+        if path == Path::new(FLOWMAP_PATH) {
+            return Some(FlowmapTexture::load(FLOWMAP_PATH).map(|tex| {
+                self.flowmap = Some(tex);
+                format!("↻ {} recargado", FLOWMAP_PATH)
+            }));
+        }
+        if path == Path::new(SPHERE_PATH) {
+            return Some(load_sphere().map(|m| {
+                let lod = sphere_levels(m);
+                let msg = format!("↻ {} recargado ({})", SPHERE_PATH, lod.mesh().summary());
+                for state in self.bodies.iter_mut().filter(|s| s.model.is_none()) {
+                    state.lod = Some(lod.clone());
+                }
+                msg
+            }));
+        }
+        if let Some((model_path, mesh)) = self.models.iter_mut().find(|(p, _)| path == Path::new(p)) {
+            return Some(load_model(model_path, &self.model_opts).map(|m| {
+                *mesh = m;
+                format!("↻ {} recargado ({})", model_path, mesh.summary())
+            }));
+        }
+        let params_path = self.scene.shader_params.as_deref().filter(|p| path == Path::new(p))?;
+        Some(ShaderParams::load(params_path).map(|p| {
+            self.shader_params = p;
+            format!("↻ {} recargado", params_path)
+        }))
+    }
+
+    // Recarga todos los modelos con otro modo de normales
+    pub fn set_normal_mode(&mut self, mode: NormalMode) {
+        self.model_opts.normals = mode;
+        for (path, mesh) in &mut self.models {
+            match load_model(path, &self.model_opts) {
+                Ok(m) => *mesh = m,
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+    }
+
+    // Malla con la que se dibuja un cuerpo: su modelo o la forma actual de los planetas
+    pub fn body_mesh(&self, body: usize, planet_shape: usize) -> &Mesh {
+        let state = &self.bodies[body];
+        match state.model {
+            Some(m) => &self.models[m].1,
+            None => match (planet_shape, &state.lod) {
+                (0, Some(lod)) => lod.mesh(),
+                _ => &self.planet_shapes[planet_shape - 1].1,
+            },
+        }
+    }
+
+    pub fn body_rings(&self, body: usize) -> Option<&Mesh> {
+        self.bodies[body].rings.as_ref()
+    }
+
+    // Coloca los cuerpos visibles en el instante `time` (y elige su nivel de detalle)
+    fn place(&mut self, view: &ViewState, time: f32, proj: &Projection, positions: &[glm::Vec3]) -> Vec<Placement> {
+        let camera = view.camera();
+        let visible: Vec<usize> = if self.scene.camera.solo { vec![view.focus] } else { (0..self.scene.bodies.len()).collect() };
+
+        let mut placements = Vec::with_capacity(visible.len());
+        for i in visible {
+            let body = &self.scene.bodies[i];
+            let state = &mut self.bodies[i];
+            let (center, max_dim, radius) = state.model.map_or(self.sphere_frame, |m| mesh_frame(&self.models[m].1));
+            let fit = body.size / max_dim;
+            let shape = if i == view.focus { body.scaling() * view.squash_matrix() } else { body.scaling() };
+            let to_mesh = glm::scaling(&glm::vec3(fit, fit, fit)) * glm::translation(&-center);
+            let place = camera * glm::translation(&positions[i]);
+            let model = place * body.orientation(time) * shape * to_mesh;
+            let scale = fit * body.scale.iter().fold(0.0, |a: f32, &s| a.max(s.abs()));
+
+            let light_dir = match self.scene.light {
+                Light::Directional { direction } => glm::vec4(direction[0], direction[1], direction[2], 0.0),
+                Light::Point { position } => camera * glm::vec4(positions[i].x - position[0], positions[i].y - position[1], positions[i].z - position[2], 0.0),
+            }.xyz();
+
+            // Nivel de detalle de la esfera según su radio proyectado
+            if let (0, Some(lod)) = (view.planet_shape, &mut state.lod) {
+                let radius_px = proj.radius_px(lod.radius(), &model);
+                if lod.select(radius_px) {
+                    println!("LOD de '{}': nivel {} ({} triángulos, radio {:.0} px)",
+                        body.name, lod.current(), lod.mesh().indices.len(), radius_px);
+                }
+            }
+
+            let ring_tilt = glm::rotation(body.rings.map_or(0.0, |r| r.tilt.to_radians()), &glm::vec3(1.0, 0.0, 0.0));
+            placements.push(Placement {
+                body: i,
+                model,
+                ring_model: place * body.tilt() * shape * ring_tilt * to_mesh,
+                origin: (model * glm::vec4(0.0, 0.0, 0.0, 1.0)).xyz(),
+                mesh_center: center,
+                center: (model * glm::vec4(center.x, center.y, center.z, 1.0)).xyz(),
+                radius: radius * scale,
+                scale,
+                light_dir: if light_dir.magnitude() > 1e-6 { light_dir.normalize() } else { glm::vec3(0.0, 0.0, -1.0) },
+            });
+        }
+        placements
+    }
+
+    // Dibuja el fondo y la escena en el instante `time` vista como dice `view`
+    pub fn draw(&mut self, renderer: &mut Renderer, view: &ViewState, time: f32) {
+        // Estado por parte de los modelos con cúpula (se reaplica tras recargarlos)
+        for (_, mesh) in &mut self.models {
+            if let Some(i) = mesh.submesh(UFO_DOME) {
+                let sub = &mut mesh.submeshes[i];
+                sub.visible = view.dome_visible;
+                sub.transform = if view.dome_lifted { glm::translation(&glm::vec3(0.0, 0.35, 0.0)) } else { glm::identity() };
+            }
+        }
+
+        // Fondo: gira con la cámara
+        let camera = view.camera();
+        let [r, g, b] = self.scene.background;
+        renderer.clear(rgb(r, g, b));
+        self.background.draw(renderer, &camera);
+
+        // Proyección centrada en el cuerpo enfocado
+        let positions = self.scene.positions(time);
+        let focus = positions[view.focus];
+        let pixels_per_unit = renderer.width.min(renderer.height) as f32 / self.scene.camera.view_size;
+        let proj = Projection {
+            center: (camera * glm::vec4(focus.x, focus.y, focus.z, 1.0)).xyz(),
+            scale: pixels_per_unit * view.zoom,
+        };
+        let placements = self.place(view, time, &proj, &positions);
+
+        let mut uniforms = Uniforms {
+            base_color: (80, 100, 140),
+            light_dir: glm::vec3(0.0, 0.0, -1.0),
+            ambient: self.scene.ambient,
+            spec_power: 50.0,
+            spec_strength: 0.45,
+            rim_strength: 0.30,
+            roughness: self.roughness,
+            time,
+            flowmap: self.flowmap.as_ref(),
+            normal_map: None,
+            rings: None,
+            environment: Some(&self.environment),
+            view_to_world: glm::mat4_to_mat3(&camera).transpose(),
+            object_origin: glm::Vec3::zeros(),
+            object_scale: 1.0,
+        };
+        let opts = DrawOptions { cull_backfaces: view.cull_backfaces, ..DrawOptions::default() };
+
+        // Uniforms propios de cada cuerpo
+        let set_body_uniforms = |uniforms: &mut Uniforms, body: &Body, p: &Placement| {
+            let [r, g, b] = body.color;
+            uniforms.base_color = (r, g, b);
+            uniforms.light_dir = p.light_dir;
+            uniforms.object_origin = p.origin;
+            uniforms.object_scale = p.scale;
+            uniforms.rings = match body.rings {
+                Some(r) if view.rings => Some(RingSystem {
+                    center: p.center,
+                    normal: (p.ring_model * glm::vec4(0.0, 1.0, 0.0, 0.0)).xyz().normalize(),
+                    planet_radius: p.radius,
+                    inner: r.inner,
+                    outer: r.outer,
+                }),
+                _ => None,
+            };
+        };
+
+        // Cuerpos opacos primero; atmósferas y anillos (sin escribir profundidad) después
+        for p in &placements {
+            let body = &self.scene.bodies[p.body];
+            let mesh = self.body_mesh(p.body, view.planet_shape);
+            set_body_uniforms(&mut uniforms, body, p);
+            uniforms.normal_map = match &body.normal_map {
+                Some(source) if view.normal_maps => self.normal_maps.iter().find(|(s, _)| s == source).map(|(_, t)| t),
+                _ => None,
+            };
+
+            let params = body.params.unwrap_or(self.shader_params);
+            let metal = MetalLambert { params: params.metal };
+            let sun = SunShader { params: params.sun };
+            let rocky = RockyPlanetShader { params: params.rocky };
+            let gas = GasGiantShader { params: params.gas_giant };
+            let active: &dyn Shader = match body.shader {
+                ShaderKind::Metal => &metal,
+                ShaderKind::Sun => &sun,
+                ShaderKind::Rocky => &rocky,
+                ShaderKind::GasGiant => &gas,
+                ShaderKind::Pbr | ShaderKind::Materials => &self.pbr_shaders[p.body],
+            };
+            let dome = mesh.submesh(UFO_DOME);
+            if body.shader == ShaderKind::Materials {
+                // Cada parte con su material del MTL
+                renderer.draw_mesh_materials(mesh, &p.model, &proj, &uniforms, opts);
+            } else if dome.is_some() {
+                // La cúpula (vidrio, sin paneles) con su propio shader, el resto con el activo
+                let hull_normal_map = uniforms.normal_map;
+                for part in 0..mesh.submeshes.len() {
+                    let is_dome = Some(part) == dome;
+                    let part_shader: &dyn Shader = if is_dome { &self.dome_shader } else { active };
+                    uniforms.normal_map = if is_dome { None } else { hull_normal_map };
+                    renderer.draw_submesh(mesh, part, &p.model, &proj, part_shader, &uniforms, opts);
+                }
+            } else {
+                renderer.draw_mesh(mesh, &p.model, &proj, active, &uniforms, opts);
+            }
+        }
+
+        for p in &placements {
+            let body = &self.scene.bodies[p.body];
+            set_body_uniforms(&mut uniforms, body, p);
+
+            // Capa de atmósfera: malla algo mayor, mezcla aditiva sobre el planeta
+            if let (true, Some(kind)) = (view.atmosphere, body.atmosphere) {
+                let params = kind.params();
+                let c = p.mesh_center;
+                let shell = glm::translation(&c)
+                    * glm::scaling(&glm::vec3(params.radius, params.radius, params.radius))
+                    * glm::translation(&-c);
+                let atmo_shader = AtmosphereShader { params, center: p.center, planet_radius: p.radius };
+                let mesh = self.body_mesh(p.body, view.planet_shape);
+                renderer.draw_mesh(mesh, &(p.model * shell), &proj, &atmo_shader, &uniforms, DrawOptions::additive());
+            }
+
+            // Anillos después del planeta: transparentes y con prueba de profundidad
+            if let (true, Some(rings)) = (view.rings, self.body_rings(p.body)) {
+                renderer.draw_mesh(rings, &p.ring_model, &proj, &RingShader, &uniforms, DrawOptions::transparent());
+            }
+        }
+    }
+}
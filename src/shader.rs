@@ -1,29 +1,33 @@
 use nalgebra_glm as glm;
 
+use crate::environment::Environment;
+use crate::shader_params::{GasGiantParams, MetalParams, RockyParams, SunParams};
+use crate::shader_ring::RingSystem;
+use crate::texture::{Filter, Sampler, Texture, Wrap};
+
+// Flowmap: R y G codifican el vector de flujo en [0,1]. Filtro trilineal,
+// repetición en longitud (u) y recorte en latitud (v) para no mezclar polos.
+#[derive(Clone)]
 pub struct FlowmapTexture {
-    pub width: usize,
-    pub height: usize,
-    pub data: Vec<u8>, // RGBA
+    pub texture: Texture,
+}
+
+impl From<Texture> for FlowmapTexture {
+    fn from(texture: Texture) -> Self {
+        let sampler = Sampler { wrap_v: Wrap::Clamp, ..Sampler::new(Filter::Trilinear, Wrap::Repeat) };
+        Self { texture: texture.with_sampler(sampler) }
+    }
 }
 
 impl FlowmapTexture {
-    pub fn sample(&self, u: f32, v: f32) -> (f32, f32, f32) {
-        let u_wrapped = u - u.floor();
-        let v_wrapped = v - v.floor();
-        
-        let x = (u_wrapped * self.width as f32) as usize % self.width;
-        let y = (v_wrapped * self.height as f32) as usize % self.height;
-        
-        let idx = (y * self.width + x) * 4;
-        let r = self.data[idx] as f32 / 255.0;
-        let g = self.data[idx + 1] as f32 / 255.0;
-        let b = self.data[idx + 2] as f32 / 255.0;
-        
-        (r, g, b)
+    pub fn load(path: &str) -> Result<Self, String> {
+        Texture::load(path).map(Self::from)
     }
-    
-    pub fn sample_flow(&self, u: f32, v: f32) -> (f32, f32) {
-        let (r, g, _) = self.sample(u, v);
+
+    // `dx`/`dy`: derivadas de (u, v) por píxel, para elegir el mipmap
+    pub fn sample_flow(&self, u: f32, v: f32, dx: glm::Vec2, dy: glm::Vec2) -> (f32, f32) {
+        let c = self.texture.sample_grad(u, v, dx, dy);
+        let (r, g) = (c.x, c.y);
         // Convertir de [0,1] a [-1,1] para vectores de flujo
         let flow_x = r * 2.0 - 1.0;
         let flow_y = g * 2.0 - 1.0;
@@ -38,8 +42,23 @@ pub struct Uniforms<'a> {
     pub spec_power: f32,
     pub spec_strength: f32,
     pub rim_strength: f32,
+    pub roughness: f32,                     // Desenfoque de los reflejos del entorno [0,1]
     pub time: f32,
     pub flowmap: Option<&'a FlowmapTexture>,
+    pub normal_map: Option<&'a Texture>, // Relieve en espacio tangente (requiere UVs y tangentes)
+    pub rings: Option<RingSystem>,          // Anillos activos (para sombras sobre el planeta)
+    pub environment: Option<&'a Environment>,
+    pub view_to_world: glm::Mat3,           // Inversa de la rotación de cámara (para muestrear el entorno)
+    pub object_origin: glm::Vec3,           // Origen de la malla en espacio vista
+    pub object_scale: f32,                  // Unidades de vista por unidad de la malla
+}
+
+impl Uniforms<'_> {
+    // Posición en vista → relativa al objeto y en unidades de su malla, para
+    // que los patrones procedurales no dependan de dónde esté ni de su tamaño
+    pub fn object_pos(&self, p: glm::Vec3) -> glm::Vec3 {
+        (p - self.object_origin) / self.object_scale
+    }
 }
 
 pub struct TriInput {
@@ -49,23 +68,105 @@ pub struct TriInput {
     pub n0: glm::Vec3,  // Normal suave del vértice 0
     pub n1: glm::Vec3,  // Normal suave del vértice 1
     pub n2: glm::Vec3,  // Normal suave del vértice 2
+    pub uv0: glm::Vec2, // UVs (cero si la malla no trae)
+    pub uv1: glm::Vec2,
+    pub uv2: glm::Vec2,
+    pub t0: glm::Vec4,  // Tangente en espacio vista + signo de la bitangente (cero sin tangentes)
+    pub t1: glm::Vec4,
+    pub t2: glm::Vec4,
+    pub c0: glm::Vec3,  // Colores por vértice (blanco si la malla no trae)
+    pub c1: glm::Vec3,
+    pub c2: glm::Vec3,
+}
+
+// Datos interpolados para un píxel (sombreado por fragmento)
+pub struct FragInput {
+    pub pos: glm::Vec3,     // Posición en espacio vista
+    pub normal: glm::Vec3,  // Normal interpolada y normalizada
+    pub uv: glm::Vec2,
+    pub tangent: glm::Vec3,   // Ortogonalizada contra la normal (cero sin tangentes)
+    pub bitangent: glm::Vec3, // cross(normal, tangent) * signo, como en MikkTSpace
+    pub color: glm::Vec3,     // Color por vértice interpolado (blanco si no hay)
+    pub uv_dx: glm::Vec2,     // Cambio de las UVs por píxel en x e y de pantalla
+    pub uv_dy: glm::Vec2,     // (constante por triángulo con la proyección ortográfica)
+    pub pos_dx: glm::Vec3,    // Ídem de la posición en espacio vista
+    pub pos_dy: glm::Vec3,
+}
+
+// Coordenadas de imagen (v hacia abajo) con sus derivadas por píxel
+#[derive(Clone, Copy)]
+pub struct ImageCoord {
+    pub st: glm::Vec2,
+    pub dx: glm::Vec2,
+    pub dy: glm::Vec2,
+}
+
+impl ImageCoord {
+    // Sin derivadas: se muestrea el nivel 0
+    pub fn at(uv: glm::Vec2) -> Self {
+        Self { st: glm::vec2(uv.x, 1.0 - uv.y), dx: glm::Vec2::zeros(), dy: glm::Vec2::zeros() }
+    }
+
+    pub fn sample(&self, tex: &Texture) -> glm::Vec4 {
+        tex.sample_grad(self.st.x, self.st.y, self.dx, self.dy)
+    }
+}
+
+impl FragInput {
+    // Las imágenes tienen la fila 0 arriba y la v de las UVs crece hacia arriba
+    pub fn image_coord(&self) -> ImageCoord {
+        let flip = |d: glm::Vec2| glm::vec2(d.x, -d.y);
+        ImageCoord { st: glm::vec2(self.uv.x, 1.0 - self.uv.y), dx: flip(self.uv_dx), dy: flip(self.uv_dy) }
+    }
+}
+
+// Normal del fragmento con un mapa de normales aplicado (si hay marco TBN)
+pub(crate) fn apply_normal_map(map: &Texture, frag:&FragInput) -> glm::Vec3 {
+    if frag.tangent.magnitude() <= 1e-6 {
+        return frag.normal;
+    }
+    let c = frag.image_coord();
+    let s = map.sample_normal(c.st.x, c.st.y, c.dx, c.dy);
+    let n = frag.tangent * s.x + frag.bitangent * s.y + frag.normal * s.z;
+    if n.magnitude() > 1e-9 { n.normalize() } else { frag.normal }
+}
+
+// Igual, con el mapa de normales de los uniforms
+pub(crate) fn mapped_normal(u:&Uniforms, frag:&FragInput) -> glm::Vec3 {
+    match u.normal_map {
+        Some(map) => apply_normal_map(map, frag),
+        None => frag.normal,
+    }
 }
 
 pub trait Shader {
     fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8);
+
+    // Los shaders que devuelven true se evalúan por píxel con `shade_fragment`
+    // (puede depender de los uniforms: p.ej. solo si hay sombras de anillos)
+    fn per_fragment(&self, _u:&Uniforms) -> bool { false }
+
+    // Color + alfa por fragmento. Por defecto: color plano del triángulo, opaco.
+    fn shade_fragment(&self, u:&Uniforms, tri:&TriInput, _frag:&FragInput) -> (u8,u8,u8,u8) {
+        let (r,g,b) = self.shade(u, tri);
+        (r,g,b,255)
+    }
 }
 
 // ============ SHADER MEJORADO: METAL ALIENÍGENA AVANZADO ============
 // Metal futurista con textura procedural, anisotropía y efectos especiales
-pub struct MetalLambert;
+pub struct MetalLambert {
+    pub params: MetalParams,
+}
 
-fn clamp01(x:f32)->f32 { x.max(0.0).min(1.0) }
+pub(crate) fn clamp01(x:f32)->f32 { x.clamp(0.0, 1.0) }
 
 impl Shader for MetalLambert {
     fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8) {
         let n_raw = (tri.p1 - tri.p0).cross(&(tri.p2 - tri.p0));
         let n = if n_raw.magnitude() > 1e-9 { n_raw.normalize() } else { glm::vec3(0.0,0.0,1.0) };
 
+        let p = &self.params;
         let l = -u.light_dir.normalize();
         let v = glm::vec3(0.0, 0.0, 1.0); // cámara mira -Z
 
@@ -73,26 +174,26 @@ impl Shader for MetalLambert {
         let ndotl = clamp01(n.dot(&l));
 
         // === PATRÓN DE PLACAS METÁLICAS (Textura procedural) ===
-        let center = (tri.p0 + tri.p1 + tri.p2) / 3.0;
+        let center = u.object_pos((tri.p0 + tri.p1 + tri.p2) / 3.0);
         
         // Patrón de paneles hexagonales/celdas
-        let panel_scale = 8.0;
+        let panel_scale = p.panel_scale;
         let panel_x = (center.x * panel_scale).sin();
         let panel_y = (center.y * panel_scale).cos();
         let panel_z = (center.z * panel_scale * 1.3).sin();
         let panel_pattern = ((panel_x + panel_y + panel_z) * 0.33).abs();
         
         // Líneas de desgaste/rayones metálicos
-        let scratch_scale = 25.0;
+        let scratch_scale = p.scratch_scale;
         let scratches = ((center.x * scratch_scale).sin() * (center.y * scratch_scale * 0.7).cos()).abs();
-        let scratch_effect = if scratches > 0.92 { 0.85 } else { 1.0 };
+        let scratch_effect = if scratches > p.scratch_threshold { p.scratch_darkening } else { 1.0 };
         
         // Variación de brillo por panel (diferentes acabados metálicos)
-        let panel_brightness = 0.85 + panel_pattern * 0.3;
+        let panel_brightness = 1.0 - p.panel_contrast * 0.5 + panel_pattern * p.panel_contrast;
 
         // === EFECTO RIM LIGHT (Borde brillante tipo sci-fi) ===
         let ndotv = clamp01(n.dot(&v));
-        let rim = (1.0 - ndotv).powf(2.5) * u.rim_strength * 1.8;
+        let rim = (1.0 - ndotv).powf(p.rim_power) * u.rim_strength * 1.8;
         
         // === SPECULAR HIGHLIGHTS (Reflexión brillante) ===
         let h = (l + v).normalize();
@@ -104,7 +205,7 @@ impl Shader for MetalLambert {
             let aniso_dir = glm::vec3(1.0, 0.0, 0.0); // dirección del pulido
             let ht = h - aniso_dir * aniso_dir.dot(&h);
             let aniso_spec = if ht.magnitude() > 0.001 {
-                0.3 * clamp01(n.dot(&ht.normalize())).powf(15.0)
+                p.aniso_strength * clamp01(n.dot(&ht.normalize())).powf(p.aniso_power)
             } else {
                 0.0
             };
@@ -114,9 +215,18 @@ impl Shader for MetalLambert {
             0.0 
         };
 
-        // === EFECTO DE METALICIDAD (reflejo ambiental simulado) ===
-        // Los metales reflejan más el ambiente en ángulos rasantes
-        let metallic_env = (1.0 - ndotv).powf(1.5) * 0.15;
+        // === REFLEJO DEL ENTORNO ===
+        // Rayo incidente hacia +Z (el z-buffer conserva la z menor), reflejado
+        // en la normal y llevado a espacio mundo para muestrear el cielo.
+        // Los metales reflejan más el ambiente en ángulos rasantes.
+        let incident = glm::vec3(0.0, 0.0, 1.0);
+        let refl = incident - n * (2.0 * incident.dot(&n));
+        let env = match u.environment {
+            Some(env) => env.sample(u.view_to_world * refl, u.roughness),
+            None => glm::vec3(0.15, 0.15, 0.15),
+        };
+        let fresnel = 0.35 + 0.65 * (1.0 - n.dot(&incident).abs()).powf(1.5);
+        let metallic_env = env * fresnel;
 
         // === COMPOSICIÓN FINAL ===
         let diffuse = u.ambient + (1.0 - u.ambient) * ndotl * 0.7;
@@ -125,38 +235,43 @@ impl Shader for MetalLambert {
         let base_intensity = diffuse * panel_brightness * scratch_effect;
         
         // Reflexiones metálicas (mantener balance)
-        let reflections = rim * 0.4 + spec * 0.8 + metallic_env * 0.3;
+        let reflections = rim * 0.4 + spec * 0.8;
         
         let final_intensity = clamp01(base_intensity + reflections);
 
-        // Color metálico preservando el tinte base
+        // Color metálico preservando el tinte base; el entorno se tiñe con él
         let (base_r, base_g, base_b) = u.base_color;
+        let tint = glm::vec3(base_r as f32, base_g as f32, base_b as f32) / 255.0;
+        let env_rgb = metallic_env.component_mul(&(tint * 0.6 + glm::vec3(0.4, 0.4, 0.4))) * 0.9;
         
-        let r = (base_r as f32 / 255.0 * final_intensity).clamp(0.0, 1.0) * 255.0;
-        let g = (base_g as f32 / 255.0 * final_intensity).clamp(0.0, 1.0) * 255.0;
-        let b = (base_b as f32 / 255.0 * final_intensity).clamp(0.0, 1.0) * 255.0;
+        let r = (tint.x * final_intensity + env_rgb.x).clamp(0.0, 1.0) * 255.0;
+        let g = (tint.y * final_intensity + env_rgb.y).clamp(0.0, 1.0) * 255.0;
+        let b = (tint.z * final_intensity + env_rgb.z).clamp(0.0, 1.0) * 255.0;
 
         (r as u8, g as u8, b as u8)
     }
 }
 
 // ============ SHADER SOL: FLOWMAP PLASMA ============
-pub struct SunShader;
+pub struct SunShader {
+    pub params: SunParams,
+}
 
 // Función de smoothstep para transiciones suaves
-fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+pub(crate) fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
     let t = clamp01((x - edge0) / (edge1 - edge0));
     t * t * (3.0 - 2.0 * t)
 }
 
 // Ruido procedural simple
 fn noise(x: f32, y: f32) -> f32 {
-    let n = (x * 12.9898 + y * 78.233).sin() * 43758.5453;
+    let n = (x * 12.9898 + y * 78.233).sin() * 43758.547;
     (n - n.floor()) * 2.0 - 1.0
 }
 
 impl Shader for SunShader {
     fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8) {
+        let p = &self.params;
         // Normal SUAVE interpolada (Phong shading)
         let n_interpolated = (tri.n0 + tri.n1 + tri.n2) / 3.0;
         let n = n_interpolated.normalize();
@@ -165,7 +280,7 @@ impl Shader for SunShader {
         let ndotv = clamp01(n.dot(&v));
         
         let center = (tri.p0 + tri.p1 + tri.p2) / 3.0;
-        let pos_normalized = center.normalize();
+        let pos_normalized = u.object_pos(center).normalize();
         
         // Convertir a coordenadas UV esféricas
         let theta = pos_normalized.y.asin();
@@ -174,48 +289,49 @@ impl Shader for SunShader {
         let uv_y = theta / std::f32::consts::PI + 0.5;
         
         // FLOWMAP: Generar campo de flujo procedural
-        let flow_scale = 3.0;
+        let flow_scale = p.flow_scale;
         let flow_x = noise(uv_x * flow_scale, uv_y * flow_scale) * 0.5 + 
                      noise(uv_x * flow_scale * 2.0, uv_y * flow_scale * 2.0) * 0.25;
         let flow_y = noise(uv_x * flow_scale + 100.0, uv_y * flow_scale + 100.0) * 0.5 +
                      noise(uv_x * flow_scale * 2.0 + 100.0, uv_y * flow_scale * 2.0 + 100.0) * 0.25;
         
         // Animar flowmap con ciclo para evitar saltos
-        let flow_cycle = 0.5;
-        let phase0 = (u.time * 0.4) % flow_cycle;
-        let phase1 = (u.time * 0.4 + flow_cycle * 0.5) % flow_cycle;
+        let flow_cycle = p.flow_cycle;
+        let phase0 = (u.time * p.flow_speed) % flow_cycle;
+        let phase1 = (u.time * p.flow_speed + flow_cycle * 0.5) % flow_cycle;
         let blend = (phase0 / flow_cycle).abs() * 2.0;
         let blend_smooth = smoothstep(0.0, 1.0, blend);
         
         // Aplicar flowmap para distorsionar UVs
-        let flow_strength = 0.15;
+        let flow_strength = p.flow_strength;
         let uv0_x = uv_x + flow_x * phase0 * flow_strength;
         let uv0_y = uv_y + flow_y * phase0 * flow_strength;
         let uv1_x = uv_x + flow_x * phase1 * flow_strength;
         let uv1_y = uv_y + flow_y * phase1 * flow_strength;
         
         // Muestrear plasma con flowmap
+        let [f0, f1] = p.plasma_frequency;
         let plasma0 = (
-            (uv0_x * 8.0).sin() * (uv0_y * 8.0).cos() +
-            (uv0_x * 15.0).cos() * (uv0_y * 15.0).sin() * 0.5
+            (uv0_x * f0).sin() * (uv0_y * f0).cos() +
+            (uv0_x * f1).cos() * (uv0_y * f1).sin() * 0.5
         ) * 0.5 + 0.5;
         
         let plasma1 = (
-            (uv1_x * 8.0).sin() * (uv1_y * 8.0).cos() +
-            (uv1_x * 15.0).cos() * (uv1_y * 15.0).sin() * 0.5
+            (uv1_x * f0).sin() * (uv1_y * f0).cos() +
+            (uv1_x * f1).cos() * (uv1_y * f1).sin() * 0.5
         ) * 0.5 + 0.5;
         
         let plasma = plasma0 * (1.0 - blend_smooth) + plasma1 * blend_smooth;
         let plasma_smooth = smoothstep(0.3, 0.7, plasma);
         
         // Manchas solares
-        let sunspot = ((uv_x * 12.0 + u.time * 0.1).sin() * (uv_y * 12.0).cos() + 1.0) * 0.5;
+        let sunspot = ((uv_x * p.sunspot_scale + u.time * p.sunspot_speed).sin() * (uv_y * p.sunspot_scale).cos() + 1.0) * 0.5;
         let sunspot_smooth = smoothstep(0.4, 0.6, sunspot);
-        let darkening = 0.78 + sunspot_smooth * 0.22;
+        let darkening = p.sunspot_darkening + sunspot_smooth * (1.0 - p.sunspot_darkening);
         
         // Corona brillante y pulsante
-        let corona_pulse = 1.0 + (u.time * 1.8).sin() * 0.1;
-        let corona = (1.0 - ndotv).powf(2.3) * 0.6 * corona_pulse;
+        let corona_pulse = 1.0 + (u.time * p.corona_pulse_speed).sin() * p.corona_pulse;
+        let corona = (1.0 - ndotv).powf(p.corona_power) * p.corona_strength * corona_pulse;
         
         // Iluminación esférica
         let sphere_lighting = smoothstep(0.0, 1.0, ndotv * 0.5 + 0.5);
@@ -225,50 +341,69 @@ impl Shader for SunShader {
         let intensity = clamp01(base_intensity * darkening * sphere_lighting + corona * 1.4);
         
         // Colores amarillo-blanco brillantes
-        let r = clamp01(intensity * 1.08 + corona * 0.3) * 255.0;
-        let g = clamp01(intensity * 0.98 + corona * 0.25) * 255.0;
-        let b = clamp01(intensity * 0.68 + corona * 0.15) * 255.0;
+        let (c, k) = (p.color, p.corona_color);
+        let r = clamp01(intensity * c[0] + corona * k[0]) * 255.0;
+        let g = clamp01(intensity * c[1] + corona * k[1]) * 255.0;
+        let b = clamp01(intensity * c[2] + corona * k[2]) * 255.0;
         
         (r as u8, g as u8, b as u8)
     }
 }
 
 // ============ SHADER PLANETA ROCOSO ============
-pub struct RockyPlanetShader;
+pub struct RockyPlanetShader {
+    pub params: RockyParams,
+}
 
 impl Shader for RockyPlanetShader {
     fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8) {
         // Normal SUAVE interpolada (Phong shading)
         let n_interpolated = (tri.n0 + tri.n1 + tri.n2) / 3.0;
         let n = n_interpolated.normalize();
-        
+        let center = (tri.p0 + tri.p1 + tri.p2) / 3.0;
+        self.shade_at(u, center, n)
+    }
+
+    // Por píxel: el relieve del mapa de normales es más fino que la malla
+    fn per_fragment(&self, _u:&Uniforms) -> bool { true }
+
+    fn shade_fragment(&self, u:&Uniforms, _tri:&TriInput, frag:&FragInput) -> (u8,u8,u8,u8) {
+        let (r,g,b) = self.shade_at(u, frag.pos, mapped_normal(u, frag));
+        (r,g,b,255)
+    }
+}
+
+impl RockyPlanetShader {
+    fn shade_at(&self, u:&Uniforms, center: glm::Vec3, n: glm::Vec3) -> (u8,u8,u8) {
+        let p = &self.params;
         let l = -u.light_dir.normalize();
         let v = glm::vec3(0.0, 0.0, 1.0);
         
         let ndotl = clamp01(n.dot(&l));
         
-        let center = (tri.p0 + tri.p1 + tri.p2) / 3.0;
-        let pos_normalized = center.normalize();
+        let pos_normalized = u.object_pos(center).normalize();
         
         // Terreno con múltiples octavas suavizadas
-        let terrain1 = ((pos_normalized.x * 3.5).sin() * (pos_normalized.y * 3.5).cos() + (pos_normalized.z * 3.5).sin()) * 0.35;
-        let terrain2 = ((pos_normalized.x * 7.5).sin() * (pos_normalized.z * 7.5).cos()) * 0.2;
-        let terrain3 = ((pos_normalized.y * 13.0).cos() * (pos_normalized.z * 13.0).sin()) * 0.12;
+        let [t1, t2, t3] = p.terrain_frequencies;
+        let terrain1 = ((pos_normalized.x * t1).sin() * (pos_normalized.y * t1).cos() + (pos_normalized.z * t1).sin()) * 0.35;
+        let terrain2 = ((pos_normalized.x * t2).sin() * (pos_normalized.z * t2).cos()) * 0.2;
+        let terrain3 = ((pos_normalized.y * t3).cos() * (pos_normalized.z * t3).sin()) * 0.12;
         let terrain_raw = (terrain1 + terrain2 + terrain3 + 1.0) * 0.5;
         let terrain = smoothstep(0.2, 0.8, terrain_raw);
         
         // Cráteres con transición suave
-        let crater1_raw = ((pos_normalized.x * 7.0).cos() * (pos_normalized.y * 7.0).sin() + 1.0) * 0.5;
-        let crater2_raw = ((pos_normalized.z * 10.0).sin() * (pos_normalized.x * 10.0).cos() + 1.0) * 0.5;
+        let [c1, c2] = p.crater_frequencies;
+        let crater1_raw = ((pos_normalized.x * c1).cos() * (pos_normalized.y * c1).sin() + 1.0) * 0.5;
+        let crater2_raw = ((pos_normalized.z * c2).sin() * (pos_normalized.x * c2).cos() + 1.0) * 0.5;
         let crater1 = smoothstep(0.35, 0.65, crater1_raw);
         let crater2 = smoothstep(0.4, 0.6, crater2_raw);
         let crater = crater1 * 0.6 + crater2 * 0.4;
-        let crater_effect = 0.72 + crater * 0.28;
+        let crater_effect = 1.0 - p.crater_depth + crater * p.crater_depth;
         
         // Casquetes polares con fade muy suave
         let pole_dist = pos_normalized.y.abs();
-        let pole_fade = smoothstep(0.55, 0.85, pole_dist);
-        let polar = 1.0 + pole_fade * 0.45;
+        let pole_fade = smoothstep(p.polar_start, p.polar_end, pole_dist);
+        let polar = 1.0 + pole_fade * p.polar_brightness;
         
         // Iluminación difusa suave con wrap-around
         let ndotl_wrapped = (ndotl + 0.3) / 1.3;
@@ -285,9 +420,10 @@ impl Shader for RockyPlanetShader {
         let intensity = clamp01(diffuse * crater_effect * polar + spec);
         
         // Colores rocosos con variación suave
-        let base_r = 135.0 + terrain * 55.0;
-        let base_g = 68.0 + terrain * 48.0 + crater * 22.0;
-        let base_b = 32.0 + terrain * 32.0;
+        let (lo, hi) = (p.lowland_color, p.highland_color);
+        let base_r = lo[0] + terrain * (hi[0] - lo[0]);
+        let base_g = lo[1] + terrain * (hi[1] - lo[1]) + crater * p.crater_tint;
+        let base_b = lo[2] + terrain * (hi[2] - lo[2]);
         
         let r = clamp01((base_r / 255.0) * intensity) * 255.0;
         let g = clamp01((base_g / 255.0) * intensity) * 255.0;
@@ -298,22 +434,42 @@ impl Shader for RockyPlanetShader {
 }
 
 // ============ SHADER GIGANTE GASEOSO: FLOWMAP PARA DISTORSIÓN UV ============
-pub struct GasGiantShader;
+pub struct GasGiantShader {
+    pub params: GasGiantParams,
+}
 
 impl Shader for GasGiantShader {
     fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8) {
         // Interpolación baricéntrica de normales SUAVES (simulando fragment shader)
         let n_interpolated = (tri.n0 + tri.n1 + tri.n2) / 3.0;
         let n = n_interpolated.normalize(); // Normal SUAVE interpolada
-        
+        let center = (tri.p0 + tri.p1 + tri.p2) / 3.0;
+        self.shade_at(u, center, n, [glm::Vec3::zeros(); 2])
+    }
+
+    // Por píxel solo con anillos: sus sombras necesitan más resolución que un triángulo
+    fn per_fragment(&self, u:&Uniforms) -> bool { u.rings.is_some() }
+
+    fn shade_fragment(&self, u:&Uniforms, _tri:&TriInput, frag:&FragInput) -> (u8,u8,u8,u8) {
+        let (r,g,b) = self.shade_at(u, frag.pos, frag.normal, [frag.pos_dx, frag.pos_dy]);
+        (r,g,b,255)
+    }
+}
+
+impl GasGiantShader {
+    fn spherical_uv(p: glm::Vec3) -> glm::Vec2 {
+        let p = p.normalize();
+        glm::vec2(p.z.atan2(p.x) / (2.0 * std::f32::consts::PI) + 0.5, p.y.asin() / std::f32::consts::PI + 0.5)
+    }
+
+    // `dpos`: derivadas de la posición por píxel (x, y), para filtrar el flowmap
+    fn shade_at(&self, u:&Uniforms, center: glm::Vec3, n: glm::Vec3, dpos: [glm::Vec3; 2]) -> (u8,u8,u8) {
+        let p = &self.params;
         let l = -u.light_dir.normalize();
-        let v = glm::vec3(0.0, 0.0, 1.0);
         
         let ndotl = clamp01(n.dot(&l));
-        let ndotv = clamp01(n.dot(&v));
         
-        let center = (tri.p0 + tri.p1 + tri.p2) / 3.0;
-        let pos_normalized = center.normalize();
+        let pos_normalized = u.object_pos(center).normalize();
         
         // Coordenadas UV esféricas BASE
         let theta = pos_normalized.y.asin();
@@ -324,17 +480,23 @@ impl Shader for GasGiantShader {
         // Calcular UVs distorsionados usando el flowmap
         let (distorted_uv_x, distorted_uv_y) = if let Some(flowmap) = u.flowmap {
             // Leer VECTORES DE FLUJO desde el flowmap (R=U, G=V)
-            let (flow_u, flow_v) = flowmap.sample_flow(base_uv_x, base_uv_y);
+            let base = glm::vec2(base_uv_x, base_uv_y);
+            let [dx, dy] = dpos.map(|d| {
+                let mut duv = Self::spherical_uv(u.object_pos(center + d)) - base;
+                duv.x -= duv.x.round(); // costura de la longitud
+                duv
+            });
+            let (flow_u, flow_v) = flowmap.sample_flow(base_uv_x, base_uv_y, dx, dy);
             
             // Ciclo temporal para animación continua sin saltos
-            let flow_cycle = 1.5;
-            let phase0 = (u.time * 0.25) % flow_cycle;
-            let phase1 = (u.time * 0.25 + flow_cycle * 0.5) % flow_cycle;
+            let flow_cycle = p.flow_cycle;
+            let phase0 = (u.time * p.flow_speed) % flow_cycle;
+            let phase1 = (u.time * p.flow_speed + flow_cycle * 0.5) % flow_cycle;
             let blend_factor = (phase0 / flow_cycle) * 2.0;
             let blend = smoothstep(0.0, 1.0, blend_factor.min(1.0));
             
             // Aplicar distorsión del flowmap con dos fases
-            let flow_strength = 0.2;
+            let flow_strength = p.flow_strength;
             let uv0_x = base_uv_x + flow_u * phase0 * flow_strength;
             let uv0_y = base_uv_y + flow_v * phase0 * flow_strength;
             let uv1_x = base_uv_x + flow_u * phase1 * flow_strength;
@@ -351,34 +513,34 @@ impl Shader for GasGiantShader {
         
         // TEXTURA PROCEDURAL DE GAS/NUBES usando UVs distorsionados
         // Múltiples octavas de ruido para atmósfera realista
-        let cloud_layer1 = ((distorted_uv_x * 8.0).sin() * (distorted_uv_y * 8.0).cos() + 
-                           (distorted_uv_y * 8.0).sin()) * 0.5 + 0.5;
-        let cloud_layer2 = ((distorted_uv_x * 16.0 + 0.5).cos() * (distorted_uv_y * 16.0 - 0.3).sin() + 1.0) * 0.5;
-        let cloud_layer3 = ((distorted_uv_x * 32.0 + 1.0).sin() * (distorted_uv_y * 32.0 + 0.7).cos() + 1.0) * 0.5;
+        let f = p.cloud_frequency;
+        let cloud_layer1 = ((distorted_uv_x * f).sin() * (distorted_uv_y * f).cos() + 
+                           (distorted_uv_y * f).sin()) * 0.5 + 0.5;
+        let cloud_layer2 = ((distorted_uv_x * f * 2.0 + 0.5).cos() * (distorted_uv_y * f * 2.0 - 0.3).sin() + 1.0) * 0.5;
+        let cloud_layer3 = ((distorted_uv_x * f * 4.0 + 1.0).sin() * (distorted_uv_y * f * 4.0 + 0.7).cos() + 1.0) * 0.5;
         
         let cloud_detail = cloud_layer1 * 0.5 + cloud_layer2 * 0.3 + cloud_layer3 * 0.2;
         let cloud_smooth = smoothstep(0.3, 0.7, cloud_detail);
         
         // Bandas atmosféricas (latitud) con las UVs distorsionadas
-        let latitude_bands = ((distorted_uv_y * 12.0).sin() + 1.0) * 0.5;
+        let latitude_bands = ((distorted_uv_y * p.band_frequency).sin() + 1.0) * 0.5;
         let band_smooth = smoothstep(0.35, 0.65, latitude_bands);
         
         // Turbulencia fina
-        let turbulence = ((distorted_uv_x * 40.0 + distorted_uv_y * 20.0).sin() + 1.0) * 0.5;
+        let turbulence = ((distorted_uv_x * p.turbulence_frequency * 2.0 + distorted_uv_y * p.turbulence_frequency).sin() + 1.0) * 0.5;
         let turb_smooth = smoothstep(0.4, 0.6, turbulence);
         
         // Gran Mancha Roja (estática en UV space)
-        let storm_center_u = 0.7;
-        let storm_center_v = 0.35;
+        let [storm_center_u, storm_center_v] = p.storm_center;
         let du = distorted_uv_x - storm_center_u;
-        let dv = (distorted_uv_y - storm_center_v) * 1.8;
+        let dv = (distorted_uv_y - storm_center_v) * p.storm_aspect;
         let storm_dist = (du * du + dv * dv).sqrt();
-        let storm_size = 0.12;
-        let storm = smoothstep(storm_size, 0.0, storm_dist);
+        let storm = smoothstep(p.storm_size, 0.0, storm_dist);
         
         // COLORES DE GAS (Júpiter-like)
-        let light_color = (210.0 + turb_smooth * 25.0, 175.0 + turb_smooth * 30.0, 130.0 + turb_smooth * 20.0);
-        let dark_color = (150.0 + turb_smooth * 20.0, 110.0 + turb_smooth * 20.0, 70.0 + turb_smooth * 15.0);
+        let (lc, dc) = (p.light_color, p.dark_color);
+        let light_color = (lc[0] + turb_smooth * 25.0, lc[1] + turb_smooth * 30.0, lc[2] + turb_smooth * 20.0);
+        let dark_color = (dc[0] + turb_smooth * 20.0, dc[1] + turb_smooth * 20.0, dc[2] + turb_smooth * 15.0);
         
         // Mezclar bandas con nubes
         let base_r = light_color.0 * band_smooth + dark_color.0 * (1.0 - band_smooth);
@@ -391,19 +553,18 @@ impl Shader for GasGiantShader {
         let cloud_b = base_b * (0.85 + cloud_smooth * 0.3);
         
         // Mezclar con tormenta roja
-        let storm_color = (200.0, 80.0, 60.0);
+        let storm_color = (p.storm_color[0], p.storm_color[1], p.storm_color[2]);
         let final_r = cloud_r * (1.0 - storm) + storm_color.0 * storm;
         let final_g = cloud_g * (1.0 - storm) + storm_color.1 * storm;
         let final_b = cloud_b * (1.0 - storm) + storm_color.2 * storm;
         
         // Iluminación volumétrica
         let ndotl_wrapped = (ndotl + 0.4) / 1.4;
-        let diffuse = u.ambient + (1.0 - u.ambient) * smoothstep(0.0, 1.0, ndotl_wrapped * 0.9);
-        
-        // Atmósfera en bordes
-        let atmosphere = smoothstep(0.0, 1.0, 1.0 - ndotv).powf(2.0) * 0.25;
+        let ring_shadow = u.rings.map_or(1.0, |rings| rings.shadow_on_planet(center, l));
+        let diffuse = u.ambient + (1.0 - u.ambient) * smoothstep(0.0, 1.0, ndotl_wrapped * 0.9) * ring_shadow;
         
-        let intensity = clamp01(diffuse + atmosphere);
+        // El brillo del borde lo pone la capa de atmósfera (`AtmosphereShader`)
+        let intensity = clamp01(diffuse);
         
         let r = clamp01((final_r / 255.0) * intensity) * 255.0;
         let g = clamp01((final_g / 255.0) * intensity) * 255.0;
@@ -0,0 +1,171 @@
+use nalgebra_glm as glm;
+use serde::Deserialize;
+
+use crate::shader::{clamp01, FragInput, Shader, TriInput, Uniforms};
+
+const VIEW_STEPS: usize  = 12;
+const LIGHT_STEPS: usize = 6;
+
+// Parámetros de dispersión. Distancias en radios planetarios y coeficientes
+// por radio planetario, para que el mismo preset sirva a cualquier escala.
+// En un archivo de escena los campos que falten son los de `rocky`.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AtmosphereParams {
+    pub radius: f32,                // Radio exterior de la capa (1.0 = superficie)
+    pub rayleigh_height: f32,       // Altura de escala Rayleigh
+    pub mie_height: f32,            // Altura de escala Mie
+    pub rayleigh_coeff: glm::Vec3,  // β_R por canal (azul dispersa más)
+    pub mie_coeff: f32,             // β_M (gris)
+    pub mie_g: f32,                 // Asimetría de Henyey-Greenstein
+    pub sun_intensity: f32,
+}
+
+impl Default for AtmosphereParams {
+    fn default() -> Self {
+        Self::rocky()
+    }
+}
+
+impl AtmosphereParams {
+    // Atmósfera tipo Tierra: limbo azul y terminador rojizo
+    pub fn rocky() -> Self {
+        Self {
+            radius: 1.06,
+            rayleigh_height: 0.015,
+            mie_height: 0.005,
+            rayleigh_coeff: glm::vec3(3.1, 7.2, 17.7),
+            mie_coeff: 5.0,
+            mie_g: 0.76,
+            sun_intensity: 30.0,
+        }
+    }
+
+    // Gigante gaseoso: capa más gruesa y neblina dominada por Mie
+    pub fn gas_giant() -> Self {
+        Self {
+            radius: 1.08,
+            rayleigh_height: 0.025,
+            mie_height: 0.012,
+            rayleigh_coeff: glm::vec3(2.2, 3.6, 6.5),
+            mie_coeff: 4.0,
+            mie_g: 0.70,
+            sun_intensity: 24.0,
+        }
+    }
+}
+
+// ============ SHADER ATMÓSFERA: DISPERSIÓN SIMPLE RAYLEIGH/MIE ============
+// Se dibuja sobre una esfera algo mayor que el planeta (mezcla aditiva) e
+// integra analíticamente la luz dispersada a lo largo del rayo de vista.
+pub struct AtmosphereShader {
+    pub params: AtmosphereParams,
+    pub center: glm::Vec3,     // Centro del planeta en espacio vista
+    pub planet_radius: f32,
+}
+
+// Intersección rayo-esfera centrada en el origen: (t_entrada, t_salida)
+fn ray_sphere(o: glm::Vec3, d: glm::Vec3, r: f32) -> Option<(f32, f32)> {
+    let b = o.dot(&d);
+    let c = o.dot(&o) - r * r;
+    let disc = b * b - c;
+    if disc < 0.0 { return None; }
+    let s = disc.sqrt();
+    Some((-b - s, -b + s))
+}
+
+impl AtmosphereShader {
+    fn scatter(&self, frag_pos: glm::Vec3, l: glm::Vec3) -> glm::Vec3 {
+        let p = &self.params;
+
+        // Rayo ortográfico por el píxel, en unidades de radio planetario.
+        // El z-buffer conserva la z menor: la cámara mira hacia +Z.
+        let rel = (frag_pos - self.center) / self.planet_radius;
+        let dir = glm::vec3(0.0, 0.0, 1.0);
+        let origin = glm::vec3(rel.x, rel.y, -2.0 * p.radius);
+
+        let Some((t0, t1)) = ray_sphere(origin, dir, p.radius) else { return glm::Vec3::zeros(); };
+        let t_start = t0.max(0.0);
+        let t_end = match ray_sphere(origin, dir, 1.0) {
+            Some((tp, _)) if tp > 0.0 => tp.min(t1),
+            _ => t1,
+        };
+        if t_end <= t_start { return glm::Vec3::zeros(); }
+
+        let ds = (t_end - t_start) / VIEW_STEPS as f32;
+        let mut od_r = 0.0;
+        let mut od_m = 0.0;
+        let mut sum_r = glm::Vec3::zeros();
+        let mut sum_m = glm::Vec3::zeros();
+
+        for i in 0..VIEW_STEPS {
+            let s = origin + dir * (t_start + ds * (i as f32 + 0.5));
+            let h = s.magnitude() - 1.0;
+            let dr = (-h / p.rayleigh_height).exp() * ds;
+            let dm = (-h / p.mie_height).exp() * ds;
+            od_r += dr;
+            od_m += dm;
+
+            // Profundidad óptica hacia el sol (sin luz si el planeta tapa)
+            let Some((_, tl)) = ray_sphere(s, l, p.radius) else { continue; };
+            if let Some((tp, _)) = ray_sphere(s, l, 1.0) && tp > 0.0 { continue; }
+            let dl = tl / LIGHT_STEPS as f32;
+            let mut odl_r = 0.0;
+            let mut odl_m = 0.0;
+            for j in 0..LIGHT_STEPS {
+                let sl = s + l * (dl * (j as f32 + 0.5));
+                let hl = sl.magnitude() - 1.0;
+                odl_r += (-hl / p.rayleigh_height).exp() * dl;
+                odl_m += (-hl / p.mie_height).exp() * dl;
+            }
+
+            let tau = p.rayleigh_coeff * (od_r + odl_r) + glm::Vec3::repeat(p.mie_coeff * 1.1 * (od_m + odl_m));
+            let atten = glm::vec3((-tau.x).exp(), (-tau.y).exp(), (-tau.z).exp());
+            sum_r += atten * dr;
+            sum_m += atten * dm;
+        }
+
+        // Funciones de fase
+        let mu = dir.dot(&l);
+        let g = p.mie_g;
+        let phase_r = 3.0 / (16.0 * std::f32::consts::PI) * (1.0 + mu * mu);
+        let phase_m = 3.0 / (8.0 * std::f32::consts::PI)
+            * ((1.0 - g * g) * (1.0 + mu * mu))
+            / ((2.0 + g * g) * (1.0 + g * g - 2.0 * g * mu).max(1e-4).powf(1.5));
+
+        (sum_r.component_mul(&p.rayleigh_coeff) * phase_r + sum_m * (p.mie_coeff * phase_m)) * p.sun_intensity
+    }
+}
+
+impl Shader for AtmosphereShader {
+    fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8) {
+        let center = (tri.p0 + tri.p1 + tri.p2) / 3.0;
+        let (r,g,b,_) = self.shade_fragment(u, tri, &FragInput {
+            pos: center,
+            normal: tri.n0,
+            uv: glm::Vec2::zeros(),
+            tangent: glm::Vec3::zeros(),
+            bitangent: glm::Vec3::zeros(),
+            color: tri.c0,
+            uv_dx: glm::Vec2::zeros(),
+            uv_dy: glm::Vec2::zeros(),
+            pos_dx: glm::Vec3::zeros(),
+            pos_dy: glm::Vec3::zeros(),
+        });
+        (r,g,b)
+    }
+
+    fn per_fragment(&self, _u:&Uniforms) -> bool { true }
+
+    fn shade_fragment(&self, u:&Uniforms, _tri:&TriInput, frag:&FragInput) -> (u8,u8,u8,u8) {
+        let l = -u.light_dir.normalize();
+        let c = self.scatter(frag.pos, l);
+
+        // Exposición para llevar la radiancia a [0,1]
+        let r = clamp01(1.0 - (-c.x).exp()) * 255.0;
+        let g = clamp01(1.0 - (-c.y).exp()) * 255.0;
+        let b = clamp01(1.0 - (-c.z).exp()) * 255.0;
+
+        (r as u8, g as u8, b as u8, 255)
+    }
+}
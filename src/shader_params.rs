@@ -0,0 +1,162 @@
+use serde::Deserialize;
+
+use crate::data_file;
+
+// Parámetros artísticos de los shaders procedurales. Se leen de un archivo
+// RON, JSON o TOML (ver `data_file`); los campos que falten toman el
+// valor por defecto y un nombre desconocido es un error, para que una errata
+// no pase desapercibida. Los colores van en [0,255] salvo donde se indique.
+
+// Metal del OVNI: paneles, rayones y brillo anisótropo
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetalParams {
+    pub panel_scale: f32,       // Frecuencia del patrón de paneles
+    pub panel_contrast: f32,    // Variación de brillo entre paneles
+    pub scratch_scale: f32,     // Frecuencia de los rayones
+    pub scratch_threshold: f32, // Umbral del patrón a partir del cual hay rayón
+    pub scratch_darkening: f32, // Brillo que conserva un rayón
+    pub aniso_strength: f32,    // Especular secundario del pulido
+    pub aniso_power: f32,
+    pub rim_power: f32,
+}
+
+impl Default for MetalParams {
+    fn default() -> Self {
+        Self {
+            panel_scale: 8.0,
+            panel_contrast: 0.3,
+            scratch_scale: 25.0,
+            scratch_threshold: 0.92,
+            scratch_darkening: 0.85,
+            aniso_strength: 0.3,
+            aniso_power: 15.0,
+            rim_power: 2.5,
+        }
+    }
+}
+
+// Sol: plasma distorsionado por un flowmap procedural, manchas y corona
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SunParams {
+    pub flow_scale: f32,          // Frecuencia del campo de flujo
+    pub flow_speed: f32,          // Avance de la fase por segundo
+    pub flow_cycle: f32,          // Duración del ciclo antes de reiniciar la fase
+    pub flow_strength: f32,       // Distorsión de las UVs
+    pub plasma_frequency: [f32; 2], // Octavas del plasma
+    pub sunspot_scale: f32,
+    pub sunspot_speed: f32,
+    pub sunspot_darkening: f32,   // Brillo que conserva una mancha
+    pub corona_power: f32,
+    pub corona_strength: f32,
+    pub corona_pulse: f32,        // Amplitud del pulso de la corona
+    pub corona_pulse_speed: f32,
+    pub color: [f32; 3],          // Factor por canal sobre la intensidad (no en [0,255])
+    pub corona_color: [f32; 3],   // Idem para la corona
+}
+
+impl Default for SunParams {
+    fn default() -> Self {
+        Self {
+            flow_scale: 3.0,
+            flow_speed: 0.4,
+            flow_cycle: 0.5,
+            flow_strength: 0.15,
+            plasma_frequency: [8.0, 15.0],
+            sunspot_scale: 12.0,
+            sunspot_speed: 0.1,
+            sunspot_darkening: 0.78,
+            corona_power: 2.3,
+            corona_strength: 0.6,
+            corona_pulse: 0.1,
+            corona_pulse_speed: 1.8,
+            color: [1.08, 0.98, 0.68],
+            corona_color: [0.3, 0.25, 0.15],
+        }
+    }
+}
+
+// Planeta rocoso: terreno, cráteres y casquetes polares
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RockyParams {
+    pub terrain_frequencies: [f32; 3], // Tres octavas del terreno
+    pub crater_frequencies: [f32; 2],
+    pub crater_depth: f32,      // Oscurecimiento máximo de un cráter
+    pub polar_start: f32,       // |y| donde empieza el casquete
+    pub polar_end: f32,         // |y| donde el casquete es pleno
+    pub polar_brightness: f32,
+    pub lowland_color: [f32; 3],
+    pub highland_color: [f32; 3],
+    pub crater_tint: f32,       // Verde extra en los cráteres
+}
+
+impl Default for RockyParams {
+    fn default() -> Self {
+        Self {
+            terrain_frequencies: [3.5, 7.5, 13.0],
+            crater_frequencies: [7.0, 10.0],
+            crater_depth: 0.28,
+            polar_start: 0.55,
+            polar_end: 0.85,
+            polar_brightness: 0.45,
+            lowland_color: [135.0, 68.0, 32.0],
+            highland_color: [190.0, 116.0, 64.0],
+            crater_tint: 22.0,
+        }
+    }
+}
+
+// Gigante gaseoso: flowmap, bandas, nubes y Gran Mancha Roja
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GasGiantParams {
+    pub flow_speed: f32,
+    pub flow_cycle: f32,
+    pub flow_strength: f32,
+    pub cloud_frequency: f32,   // Primera octava de nubes (las otras al doble y al cuádruple)
+    pub band_frequency: f32,    // Bandas de latitud
+    pub turbulence_frequency: f32,
+    pub storm_center: [f32; 2], // Posición de la tormenta en UV
+    pub storm_size: f32,
+    pub storm_aspect: f32,      // Achatamiento en latitud
+    pub storm_color: [f32; 3],
+    pub light_color: [f32; 3],  // Bandas claras
+    pub dark_color: [f32; 3],   // Bandas oscuras
+}
+
+impl Default for GasGiantParams {
+    fn default() -> Self {
+        Self {
+            flow_speed: 0.25,
+            flow_cycle: 1.5,
+            flow_strength: 0.2,
+            cloud_frequency: 8.0,
+            band_frequency: 12.0,
+            turbulence_frequency: 20.0,
+            storm_center: [0.7, 0.35],
+            storm_size: 0.12,
+            storm_aspect: 1.8,
+            storm_color: [200.0, 80.0, 60.0],
+            light_color: [210.0, 175.0, 130.0],
+            dark_color: [150.0, 110.0, 70.0],
+        }
+    }
+}
+
+// Todos los shaders juntos, tal como aparecen en el archivo
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShaderParams {
+    pub metal: MetalParams,
+    pub sun: SunParams,
+    pub rocky: RockyParams,
+    pub gas_giant: GasGiantParams,
+}
+
+impl ShaderParams {
+    pub fn load(path: &str) -> Result<Self, String> {
+        data_file::load(path)
+    }
+}
@@ -0,0 +1,159 @@
+use nalgebra_glm as glm;
+use serde::Deserialize;
+
+use crate::shader::{clamp01, mapped_normal, FragInput, Shader, TriInput, Uniforms};
+
+const PI: f32 = std::f32::consts::PI;
+
+// Intensidad de la luz direccional de la escena (radiancia lineal)
+const LIGHT_INTENSITY: f32 = 3.2;
+
+// Material metálico-rugoso. Colores en espacio lineal [0,1]. En un archivo
+// de escena los campos que falten son los del casco del OVNI.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PbrMaterial {
+    pub base_color: glm::Vec3,
+    pub metallic: f32,
+    pub roughness: f32,
+    pub emissive: glm::Vec3,
+}
+
+#[inline]
+pub(crate) fn srgb_to_linear(c: u8) -> f32 {
+    (c as f32 / 255.0).powf(2.2)
+}
+
+#[inline]
+fn linear_to_srgb(c: f32) -> f32 {
+    clamp01(c).powf(1.0 / 2.2)
+}
+
+impl Default for PbrMaterial {
+    fn default() -> Self {
+        Self::ufo_hull()
+    }
+}
+
+impl PbrMaterial {
+    pub fn from_srgb(base: (u8, u8, u8), metallic: f32, roughness: f32) -> Self {
+        Self {
+            base_color: glm::vec3(srgb_to_linear(base.0), srgb_to_linear(base.1), srgb_to_linear(base.2)),
+            metallic: clamp01(metallic),
+            roughness: roughness.clamp(0.04, 1.0),
+            emissive: glm::Vec3::zeros(),
+        }
+    }
+
+    // Casco del OVNI: metal azul oscuro pulido
+    pub fn ufo_hull() -> Self {
+        Self::from_srgb((80, 100, 140), 0.9, 0.3)
+    }
+
+    // Cúpula del OVNI: dieléctrico liso con un brillo interior tenue
+    pub fn ufo_dome() -> Self {
+        let mut m = Self::from_srgb((110, 190, 220), 0.0, 0.08);
+        m.emissive = glm::vec3(0.02, 0.06, 0.08);
+        m
+    }
+}
+
+// ============ SHADER PBR: COOK-TORRANCE GGX ============
+// Difuso de Lambert + especular GGX con Fresnel de Schlick y geometría de
+// Smith (Schlick-GGX). El ambiente sale del mapa de entorno si existe.
+pub struct PbrShader {
+    pub material: PbrMaterial,
+}
+
+// Distribución de normales GGX / Trowbridge-Reitz
+fn distribution_ggx(ndoth: f32, roughness: f32) -> f32 {
+    let a = roughness * roughness;
+    let a2 = a * a;
+    let d = ndoth * ndoth * (a2 - 1.0) + 1.0;
+    a2 / (PI * d * d).max(1e-7)
+}
+
+// Término de Smith con Schlick-GGX (k para luz directa)
+fn geometry_smith(ndotv: f32, ndotl: f32, roughness: f32) -> f32 {
+    let r = roughness + 1.0;
+    let k = r * r / 8.0;
+    let g1 = |x: f32| x / (x * (1.0 - k) + k);
+    g1(ndotv) * g1(ndotl)
+}
+
+fn fresnel_schlick(cos_theta: f32, f0: glm::Vec3) -> glm::Vec3 {
+    let f = (1.0 - cos_theta).clamp(0.0, 1.0).powi(5);
+    f0 + (glm::vec3(1.0, 1.0, 1.0) - f0) * f
+}
+
+// Aproximación analítica de la integral de la BRDF de entorno (Karis, móvil)
+fn env_brdf_approx(f0: glm::Vec3, roughness: f32, ndotv: f32) -> glm::Vec3 {
+    let c0 = glm::vec4(-1.0, -0.0275, -0.572, 0.022);
+    let c1 = glm::vec4(1.0, 0.0425, 1.04, -0.04);
+    let r = c0 * roughness + c1;
+    let a004 = (r.x * r.x).min((-9.28 * ndotv).exp2()) * r.x + r.y;
+    let ab = glm::vec2(-1.04, 1.04) * a004 + glm::vec2(r.z, r.w);
+    f0 * ab.x + glm::Vec3::repeat(ab.y)
+}
+
+impl PbrShader {
+    pub(crate) fn shade_at(&self, u:&Uniforms, n: glm::Vec3) -> (u8,u8,u8) {
+        let m = &self.material;
+        let n = glm::normalize(&n);
+        let l = -u.light_dir.normalize();
+        // Hacia la cámara: el z-buffer conserva la z menor (cámara en -Z)
+        let v = glm::vec3(0.0, 0.0, -1.0);
+        let h = glm::normalize(&(l + v));
+
+        let ndotl = clamp01(n.dot(&l));
+        let ndotv = clamp01(n.dot(&v)).max(1e-4);
+        let ndoth = clamp01(n.dot(&h));
+        let vdoth = clamp01(v.dot(&h));
+
+        let f0 = glm::vec3(0.04, 0.04, 0.04) * (1.0 - m.metallic) + m.base_color * m.metallic;
+
+        // Luz directa
+        let f = fresnel_schlick(vdoth, f0);
+        let d = distribution_ggx(ndoth, m.roughness);
+        let g = geometry_smith(ndotv, ndotl, m.roughness);
+        let specular = f * (d * g / (4.0 * ndotv * ndotl).max(1e-4));
+        let kd = (glm::vec3(1.0, 1.0, 1.0) - f) * (1.0 - m.metallic);
+        let diffuse = kd.component_mul(&m.base_color) / PI;
+        let direct = (diffuse + specular) * (LIGHT_INTENSITY * ndotl);
+
+        // Ambiente: irradiancia (entorno muy desenfocado) + reflejo prefiltrado
+        let (irradiance, prefiltered) = match u.environment {
+            Some(env) => {
+                let refl = -v - n * (2.0 * (-v).dot(&n));
+                (env.sample(u.view_to_world * n, 1.0) + glm::Vec3::repeat(u.ambient * 0.5),
+                 env.sample(u.view_to_world * refl, m.roughness))
+            }
+            None => (glm::Vec3::repeat(u.ambient), glm::Vec3::repeat(u.ambient)),
+        };
+        let ambient_diffuse = irradiance.component_mul(&m.base_color) * (1.0 - m.metallic);
+        let ambient_specular = prefiltered.component_mul(&env_brdf_approx(f0, m.roughness, ndotv));
+
+        let color = direct + ambient_diffuse + ambient_specular + m.emissive;
+
+        // Tonemapping de Reinhard y vuelta a sRGB
+        let mapped = color.component_div(&(color + glm::vec3(1.0, 1.0, 1.0)));
+        (
+            (linear_to_srgb(mapped.x) * 255.0) as u8,
+            (linear_to_srgb(mapped.y) * 255.0) as u8,
+            (linear_to_srgb(mapped.z) * 255.0) as u8,
+        )
+    }
+}
+
+impl Shader for PbrShader {
+    fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8) {
+        self.shade_at(u, tri.n0 + tri.n1 + tri.n2)
+    }
+
+    fn per_fragment(&self, _u:&Uniforms) -> bool { true }
+
+    fn shade_fragment(&self, u:&Uniforms, _tri:&TriInput, frag:&FragInput) -> (u8,u8,u8,u8) {
+        let (r,g,b) = self.shade_at(u, mapped_normal(u, frag));
+        (r,g,b,255)
+    }
+}
@@ -0,0 +1,118 @@
+use nalgebra_glm as glm;
+
+use crate::shader::{clamp01, smoothstep, FragInput, Shader, TriInput, Uniforms};
+
+// ============ SISTEMA DE ANILLOS (geometría compartida para sombras) ============
+// Todo en espacio vista. Los radios están en unidades de radio planetario.
+#[derive(Clone, Copy)]
+pub struct RingSystem {
+    pub center: glm::Vec3,       // Centro del planeta
+    pub normal: glm::Vec3,       // Normal del plano de los anillos
+    pub planet_radius: f32,
+    pub inner: f32,
+    pub outer: f32,
+}
+
+impl RingSystem {
+    // Radio (en radios planetarios) de un punto proyectado sobre el plano
+    pub fn radial(&self, p: glm::Vec3) -> f32 {
+        let d = p - self.center;
+        let in_plane = d - self.normal * d.dot(&self.normal);
+        in_plane.magnitude() / self.planet_radius
+    }
+
+    // Densidad de partículas [0,1] con bandas radiales y divisiones
+    pub fn density(&self, r: f32) -> f32 {
+        if r < self.inner || r > self.outer { return 0.0; }
+        let t = (r - self.inner) / (self.outer - self.inner);
+
+        // Bordes suaves y anillo interior (tipo C) más tenue
+        let edges = smoothstep(0.0, 0.04, t) * smoothstep(1.0, 0.93, t);
+        let inner_faint = 0.35 + 0.65 * smoothstep(0.18, 0.30, t);
+
+        // Bandas finas superpuestas
+        let bands = 0.60
+            + 0.20 * (t * 61.0).sin()
+            + 0.12 * (t * 143.0 + 1.3).sin()
+            + 0.06 * (t * 377.0 + 0.4).sin();
+
+        // División de Cassini y de Encke
+        let cassini = smoothstep(0.012, 0.030, (t - 0.64).abs());
+        let encke   = smoothstep(0.003, 0.008, (t - 0.90).abs());
+
+        clamp01(edges * inner_faint * bands * cassini * encke)
+    }
+
+    // Luz transmitida [0,1] hacia un punto del planeta a través de los anillos
+    pub fn shadow_on_planet(&self, p: glm::Vec3, l: glm::Vec3) -> f32 {
+        let denom = l.dot(&self.normal);
+        if denom.abs() < 1e-5 { return 1.0; }
+        let t = (self.center - p).dot(&self.normal) / denom;
+        if t <= 0.0 { return 1.0; }
+        let hit = p + l * t;
+        1.0 - self.density(self.radial(hit)) * 0.85
+    }
+
+    // Luz [0,1] que llega a un punto de los anillos (sombra del planeta)
+    pub fn planet_shadow(&self, p: glm::Vec3, l: glm::Vec3) -> f32 {
+        let oc = self.center - p;
+        let tca = oc.dot(&l);
+        if tca <= 0.0 { return 1.0; }
+        let d = (oc.magnitude_squared() - tca * tca).max(0.0).sqrt();
+        smoothstep(self.planet_radius * 0.97, self.planet_radius * 1.03, d)
+    }
+}
+
+// ============ SHADER ANILLOS PLANETARIOS ============
+// Bandas radiales semitransparentes iluminadas por la luz de la escena.
+// Lee la geometría de `Uniforms::rings`; sin ella no dibuja nada.
+pub struct RingShader;
+
+impl RingShader {
+    fn shade_at(&self, u:&Uniforms, p: glm::Vec3, n: glm::Vec3) -> (u8,u8,u8,u8) {
+        let Some(rings) = u.rings else { return (0, 0, 0, 0); };
+
+        let r = rings.radial(p);
+        let density = rings.density(r);
+        if density < 0.01 { return (0, 0, 0, 0); }
+
+        let l = -u.light_dir.normalize();
+
+        // Iluminación de doble cara + sombra del planeta
+        let ndotl = n.dot(&l).abs();
+        let lit = rings.planet_shadow(p, l);
+        let diffuse = u.ambient + (1.0 - u.ambient) * (0.35 + 0.65 * ndotl) * lit;
+
+        // Color: bandas densas más claras, zonas tenues grisáceas
+        let t = clamp01((r - rings.inner) / (rings.outer - rings.inner));
+        let tint = 0.5 + 0.5 * (t * 23.0 + 0.7).sin();
+        let light_color = (222.0, 204.0, 168.0);
+        let dark_color  = (150.0, 138.0, 118.0);
+        let mix = clamp01(density * 0.6 + tint * 0.4);
+        let base_r = dark_color.0 + (light_color.0 - dark_color.0) * mix;
+        let base_g = dark_color.1 + (light_color.1 - dark_color.1) * mix;
+        let base_b = dark_color.2 + (light_color.2 - dark_color.2) * mix;
+
+        let r = clamp01((base_r / 255.0) * diffuse) * 255.0;
+        let g = clamp01((base_g / 255.0) * diffuse) * 255.0;
+        let b = clamp01((base_b / 255.0) * diffuse) * 255.0;
+        let a = clamp01(density * 0.92) * 255.0;
+
+        (r as u8, g as u8, b as u8, a as u8)
+    }
+}
+
+impl Shader for RingShader {
+    fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8) {
+        let center = (tri.p0 + tri.p1 + tri.p2) / 3.0;
+        let n = (tri.n0 + tri.n1 + tri.n2).normalize();
+        let (r,g,b,_) = self.shade_at(u, center, n);
+        (r,g,b)
+    }
+
+    fn per_fragment(&self, _u:&Uniforms) -> bool { true }
+
+    fn shade_fragment(&self, u:&Uniforms, _tri:&TriInput, frag:&FragInput) -> (u8,u8,u8,u8) {
+        self.shade_at(u, frag.pos, frag.normal)
+    }
+}
@@ -0,0 +1,86 @@
+use nalgebra_glm as glm;
+
+use crate::material::{Material, PbrParams};
+use crate::shader::{apply_normal_map, clamp01, FragInput, ImageCoord, Shader, TriInput, Uniforms};
+use crate::shader_pbr::{PbrMaterial, PbrShader};
+
+// ============ SHADER TEXTURIZADO: MATERIALES MTL / glTF ============
+// Blinn-Phong con los parámetros del MTL: Kd (por map_Kd si existe), Ks, Ns,
+// relieve por map_Bump y opacidad d como alfa. Los materiales con parámetros
+// PBR (glTF) se delegan en el Cook-Torrance de PbrShader.
+pub struct TexturedShader<'a> {
+    pub material: &'a Material,
+}
+
+impl TexturedShader<'_> {
+    // `tint`: color por vértice, multiplica al color difuso / base
+    fn shade_at(&self, u:&Uniforms, n: glm::Vec3, uv: ImageCoord, tint: glm::Vec3) -> (u8,u8,u8,u8) {
+        let m = self.material;
+        let alpha = (m.opacity * 255.0).round() as u8;
+        if let Some(pbr) = &m.pbr {
+            let (r,g,b) = self.shade_pbr(u, pbr, n, uv, tint);
+            return (r,g,b,alpha);
+        }
+
+        let l = -u.light_dir.normalize();
+        // Hacia la cámara: el z-buffer conserva la z menor (cámara en -Z)
+        let v = glm::vec3(0.0, 0.0, -1.0);
+        let h = glm::normalize(&(l + v));
+
+        let mut albedo = m.diffuse.component_mul(&tint);
+        if let Some(tex) = &m.diffuse_map {
+            albedo = albedo.component_mul(&uv.sample(tex).xyz());
+        }
+
+        let ndotl = clamp01(n.dot(&l));
+        let spec = if ndotl > 0.0 { clamp01(n.dot(&h)).powf(m.shininess) } else { 0.0 };
+
+        let color = albedo * (u.ambient + (1.0 - u.ambient) * ndotl) + m.specular * spec;
+        (
+            (clamp01(color.x) * 255.0) as u8,
+            (clamp01(color.y) * 255.0) as u8,
+            (clamp01(color.z) * 255.0) as u8,
+            alpha,
+        )
+    }
+
+    fn shade_pbr(&self, u:&Uniforms, pbr: &PbrParams, n: glm::Vec3, uv: ImageCoord, tint: glm::Vec3) -> (u8,u8,u8) {
+        let m = self.material;
+        let mut material = PbrMaterial {
+            base_color: m.diffuse.component_mul(&tint),
+            metallic: pbr.metallic,
+            roughness: pbr.roughness,
+            emissive: pbr.emissive,
+        };
+        if let Some(tex) = &m.diffuse_map {
+            // Se filtra en sRGB y luego se pasa a lineal
+            let linear = uv.sample(tex).xyz().map(|c| c.powf(2.2));
+            material.base_color = material.base_color.component_mul(&linear);
+        }
+        if let Some(tex) = &pbr.metallic_roughness_map {
+            let c = uv.sample(tex);
+            let (rough, metal) = (c.y, c.z);
+            material.roughness = (material.roughness * rough).clamp(0.04, 1.0);
+            material.metallic *= metal;
+        }
+        PbrShader { material }.shade_at(u, n)
+    }
+}
+
+impl Shader for TexturedShader<'_> {
+    fn shade(&self, u:&Uniforms, tri:&TriInput) -> (u8,u8,u8) {
+        let n = (tri.n0 + tri.n1 + tri.n2).normalize();
+        let (r,g,b,_) = self.shade_at(u, n, ImageCoord::at((tri.uv0 + tri.uv1 + tri.uv2) / 3.0), (tri.c0 + tri.c1 + tri.c2) / 3.0);
+        (r,g,b)
+    }
+
+    fn per_fragment(&self, _u:&Uniforms) -> bool { true }
+
+    fn shade_fragment(&self, u:&Uniforms, _tri:&TriInput, frag:&FragInput) -> (u8,u8,u8,u8) {
+        let n = match &self.material.normal_map {
+            Some(map) => apply_normal_map(map, frag),
+            None => frag.normal,
+        };
+        self.shade_at(u, n, frag.image_coord(), frag.color)
+    }
+}
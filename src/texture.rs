@@ -0,0 +1,499 @@
+use nalgebra_glm as glm;
+use std::path::Path;
+
+// Formato de los texels en memoria. Los de 8 bits se leen en [0,1]; los
+// de 32 bits se devuelven tal cual (alturas, HDR).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextureFormat {
+    Rgba8,
+    Rgb8,
+    R8,
+    R32F,
+    Rgba32F,
+}
+
+impl TextureFormat {
+    pub fn channels(self) -> usize {
+        match self {
+            TextureFormat::Rgba8 | TextureFormat::Rgba32F => 4,
+            TextureFormat::Rgb8 => 3,
+            TextureFormat::R8 | TextureFormat::R32F => 1,
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, TextureFormat::R32F | TextureFormat::Rgba32F)
+    }
+}
+
+// Trilineal mezcla dos niveles de mipmap elegidos por las derivadas de las
+// UVs en pantalla; sin derivadas (o con los otros filtros) se usa el nivel 0
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Filter {
+    Nearest,
+    Bilinear,
+    Trilinear,
+}
+
+// Qué hacer con las coordenadas fuera de [0,1]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wrap {
+    Repeat,
+    Clamp,
+    Mirror,
+}
+
+impl Wrap {
+    fn apply(self, i: i64, size: usize) -> usize {
+        let n = size as i64;
+        let i = match self {
+            Wrap::Repeat => i.rem_euclid(n),
+            Wrap::Clamp => i.clamp(0, n - 1),
+            Wrap::Mirror => {
+                let m = i.rem_euclid(2 * n);
+                if m >= n { 2 * n - 1 - m } else { m }
+            }
+        };
+        i as usize
+    }
+}
+
+// Filtro y direccionamiento por eje. `anisotropy` > 1 reparte hasta ese
+// número de muestras trilineales a lo largo del eje largo de la huella del
+// píxel (superficies vistas de canto).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sampler {
+    pub filter: Filter,
+    pub wrap_u: Wrap,
+    pub wrap_v: Wrap,
+    pub anisotropy: u32,
+}
+
+impl Sampler {
+    pub fn new(filter: Filter, wrap: Wrap) -> Self {
+        Self { filter, wrap_u: wrap, wrap_v: wrap, anisotropy: 1 }
+    }
+
+    pub fn anisotropic(max_samples: u32) -> Self {
+        Self { anisotropy: max_samples.max(1), ..Self::default() }
+    }
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new(Filter::Trilinear, Wrap::Repeat)
+    }
+}
+
+#[derive(Clone)]
+enum Texels {
+    U8(Vec<u8>),
+    F32(Vec<f32>),
+}
+
+// Un nivel de la cadena de mipmaps
+#[derive(Clone)]
+struct Level {
+    width: usize,
+    height: usize,
+    texels: Texels,
+}
+
+impl Level {
+    fn texel(&self, format: TextureFormat, x: usize, y: usize) -> glm::Vec4 {
+        let x = x.min(self.width - 1);
+        let y = y.min(self.height - 1);
+        let c = format.channels();
+        let i = (y * self.width + x) * c;
+        let v = |k: usize| match &self.texels {
+            Texels::U8(d) => d[i + k] as f32 / 255.0,
+            Texels::F32(d) => d[i + k],
+        };
+        match c {
+            1 => glm::vec4(v(0), v(0), v(0), 1.0),
+            3 => glm::vec4(v(0), v(1), v(2), 1.0),
+            _ => glm::vec4(v(0), v(1), v(2), v(3)),
+        }
+    }
+
+    // Mitad de tamaño, promediando bloques de 2x2 (recortados en el borde
+    // si la dimensión es impar)
+    fn downsample(&self, format: TextureFormat) -> Level {
+        let (width, height) = ((self.width / 2).max(1), (self.height / 2).max(1));
+        let channels = format.channels();
+        let mut values = Vec::with_capacity(width * height * channels);
+        for y in 0..height {
+            for x in 0..width {
+                let (x0, y0) = (x * 2, y * 2);
+                let sum = self.texel(format, x0, y0)
+                    + self.texel(format, x0 + 1, y0)
+                    + self.texel(format, x0, y0 + 1)
+                    + self.texel(format, x0 + 1, y0 + 1);
+                values.extend_from_slice(&(sum * 0.25).as_slice()[..channels]);
+            }
+        }
+        Level { width, height, texels: Texels::from_values(format, values) }
+    }
+}
+
+impl Texels {
+    fn from_values(format: TextureFormat, values: Vec<f32>) -> Self {
+        if format.is_float() {
+            Texels::F32(values)
+        } else {
+            Texels::U8(values.into_iter().map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8).collect())
+        }
+    }
+}
+
+// Imagen 2D con su cadena de mipmaps y su muestreador por defecto. La fila
+// 0 es la de arriba; las coordenadas (u, v) van de (0,0) arriba a la
+// izquierda a (1,1) abajo a la derecha (quien use UVs con v hacia arriba
+// muestrea en 1 - v y cambia de signo la derivada de v).
+#[derive(Clone)]
+pub struct Texture {
+    pub width: usize,
+    pub height: usize,
+    pub format: TextureFormat,
+    pub sampler: Sampler,
+    levels: Vec<Level>, // 0 = imagen original, luego mitades hasta 1x1
+}
+
+impl Texture {
+    fn check_size(width: usize, height: usize, format: TextureFormat, len: usize) -> Result<(), String> {
+        if width == 0 || height == 0 {
+            return Err("textura vacía".into());
+        }
+        if len != width * height * format.channels() {
+            return Err(format!("textura {}x{} {:?}: se esperaban {} valores y hay {}",
+                width, height, format, width * height * format.channels(), len));
+        }
+        Ok(())
+    }
+
+    // Texels de 8 bits (RGBA8, RGB8 o R8)
+    pub fn from_u8(width: usize, height: usize, format: TextureFormat, data: Vec<u8>) -> Result<Self, String> {
+        if format.is_float() {
+            return Err(format!("{:?} no es un formato de 8 bits", format));
+        }
+        Self::check_size(width, height, format, data.len())?;
+        Ok(Self::with_mipmaps(width, height, format, Texels::U8(data)))
+    }
+
+    // Genera la textura texel a texel; `f(x, y)` devuelve RGBA (en [0,1]
+    // para los formatos de 8 bits) y se guardan los canales del formato.
+    pub fn from_fn(width: usize, height: usize, format: TextureFormat, mut f: impl FnMut(usize, usize) -> [f32; 4]) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        let channels = format.channels();
+        let mut values = Vec::with_capacity(width * height * channels);
+        for y in 0..height {
+            for x in 0..width {
+                values.extend_from_slice(&f(x, y)[..channels]);
+            }
+        }
+        Self::with_mipmaps(width, height, format, Texels::from_values(format, values))
+    }
+
+    fn with_mipmaps(width: usize, height: usize, format: TextureFormat, texels: Texels) -> Self {
+        let mut levels = vec![Level { width, height, texels }];
+        while let Some(last) = levels.last().filter(|l| l.width > 1 || l.height > 1) {
+            let next = last.downsample(format);
+            levels.push(next);
+        }
+        Self { width, height, format, sampler: Sampler::default(), levels }
+    }
+
+
+    // Conserva los canales de la imagen: gris → R8 (R32F si es de 16 bits,
+    // p. ej. mapas de alturas), RGB → RGB8, flotantes → RGBA32F y el resto
+    // como RGBA8
+    pub fn from_image(img: image::DynamicImage) -> Self {
+        use image::DynamicImage as D;
+        let (width, height) = (img.width() as usize, img.height() as usize);
+        let (format, texels) = match img {
+            D::ImageLuma8(i) => (TextureFormat::R8, Texels::U8(i.into_raw())),
+            D::ImageLuma16(_) => (TextureFormat::R32F, Texels::F32(img.to_luma32f().into_raw())),
+            D::ImageRgb8(i) => (TextureFormat::Rgb8, Texels::U8(i.into_raw())),
+            D::ImageRgb32F(_) | D::ImageRgba32F(_) => (TextureFormat::Rgba32F, Texels::F32(img.to_rgba32f().into_raw())),
+            other => (TextureFormat::Rgba8, Texels::U8(other.to_rgba8().into_raw())),
+        };
+        Self::with_mipmaps(width, height, format, texels)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let img = image::open(path).map_err(|e| format!("Error cargando '{}': {}", path, e))?;
+        if img.width() == 0 || img.height() == 0 {
+            return Err(format!("Imagen vacía: '{}'", path));
+        }
+        Ok(Self::from_image(img))
+    }
+
+    pub fn with_sampler(mut self, sampler: Sampler) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    // Texel como RGBA; R se replica en gris y sin alfa vale 1. Las
+    // coordenadas fuera de rango se recortan.
+    pub fn texel(&self, x: usize, y: usize) -> glm::Vec4 {
+        self.levels[0].texel(self.format, x, y)
+    }
+
+    fn fetch(&self, s: &Sampler, level: &Level, x: i64, y: i64) -> glm::Vec4 {
+        level.texel(self.format, s.wrap_u.apply(x, level.width), s.wrap_v.apply(y, level.height))
+    }
+
+    fn sample_level(&self, s: &Sampler, level: &Level, u: f32, v: f32, filter: Filter) -> glm::Vec4 {
+        let fx = u * level.width as f32;
+        let fy = v * level.height as f32;
+        if !(fx.is_finite() && fy.is_finite()) {
+            return level.texel(self.format, 0, 0);
+        }
+        if filter == Filter::Nearest {
+            return self.fetch(s, level, fx.floor() as i64, fy.floor() as i64);
+        }
+        // Centros de texel en medios enteros
+        let (fx, fy) = (fx - 0.5, fy - 0.5);
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let top = self.fetch(s, level, x0, y0) * (1.0 - tx) + self.fetch(s, level, x0 + 1, y0) * tx;
+        let bottom = self.fetch(s, level, x0, y0 + 1) * (1.0 - tx) + self.fetch(s, level, x0 + 1, y0 + 1) * tx;
+        top * (1.0 - ty) + bottom * ty
+    }
+
+    // Mezcla lineal entre los dos niveles que rodean `lod`
+    fn sample_lod(&self, s: &Sampler, u: f32, v: f32, lod: f32) -> glm::Vec4 {
+        let max = (self.levels.len() - 1) as f32;
+        let lod = if lod.is_nan() { 0.0 } else { lod.clamp(0.0, max) };
+        let l0 = lod.floor() as usize;
+        let t = lod - l0 as f32;
+        let a = self.sample_level(s, &self.levels[l0], u, v, Filter::Bilinear);
+        if t <= 0.0 {
+            return a;
+        }
+        a * (1.0 - t) + self.sample_level(s, &self.levels[l0 + 1], u, v, Filter::Bilinear) * t
+    }
+
+    // Huella del píxel en texels del nivel 0: nivel de mipmap, número de
+    // muestras anisótropas y eje (en uv) sobre el que se reparten
+    fn footprint(&self, s: &Sampler, dx: glm::Vec2, dy: glm::Vec2) -> (f32, usize, glm::Vec2) {
+        let size = glm::vec2(self.width as f32, self.height as f32);
+        let (lx, ly) = (dx.component_mul(&size).magnitude(), dy.component_mul(&size).magnitude());
+        let (major, minor, axis) = if lx >= ly { (lx, ly, dx) } else { (ly, lx, dy) };
+        let samples = if s.anisotropy > 1 && major > 1.0 {
+            (major / minor.max(1e-6)).ceil().clamp(1.0, s.anisotropy as f32) as usize
+        } else {
+            1
+        };
+        ((major / samples as f32).max(1e-12).log2(), samples, axis)
+    }
+
+    // Muestra con `s` a partir de las derivadas de (u, v) por píxel en x e
+    // y de pantalla, que fijan el nivel de mipmap y la anisotropía
+    pub fn sample_grad_with(&self, s: &Sampler, u: f32, v: f32, dx: glm::Vec2, dy: glm::Vec2) -> glm::Vec4 {
+        if s.filter != Filter::Trilinear {
+            return self.sample_level(s, &self.levels[0], u, v, s.filter);
+        }
+        let (lod, samples, axis) = self.footprint(s, dx, dy);
+        if samples == 1 {
+            return self.sample_lod(s, u, v, lod);
+        }
+        let mut sum = glm::Vec4::zeros();
+        for k in 0..samples {
+            let t = (k as f32 + 0.5) / samples as f32 - 0.5;
+            sum += self.sample_lod(s, u + axis.x * t, v + axis.y * t, lod);
+        }
+        sum / samples as f32
+    }
+
+    pub fn sample_grad(&self, u: f32, v: f32, dx: glm::Vec2, dy: glm::Vec2) -> glm::Vec4 {
+        self.sample_grad_with(&self.sampler, u, v, dx, dy)
+    }
+
+    // Mapa de normales: de [0,1] a un vector en espacio tangente
+    pub fn sample_normal(&self, u: f32, v: f32, dx: glm::Vec2, dy: glm::Vec2) -> glm::Vec3 {
+        let c = self.sample_grad(u, v, dx, dy);
+        glm::vec3(c.x * 2.0 - 1.0, c.y * 2.0 - 1.0, c.z * 2.0 - 1.0)
+    }
+
+    // Copia en RGBA8 (para guardar como imagen)
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.width * self.height * 4);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let c = self.texel(x, y);
+                out.extend(c.iter().map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8));
+            }
+        }
+        out
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let img = image::RgbaImage::from_raw(self.width as u32, self.height as u32, self.to_rgba8())
+            .ok_or_else(|| format!("textura inválida para '{}'", path.display()))?;
+        img.save(path).map_err(|e| format!("Error guardando '{}': {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: glm::Vec4, b: [f32; 4]) -> bool {
+        a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5)
+    }
+
+    // 2x2 de un canal: 0.0 0.2 / 0.4 0.6
+    fn gray_2x2() -> Texture {
+        Texture::from_u8(2, 2, TextureFormat::R8, vec![0, 51, 102, 153]).unwrap()
+    }
+
+    fn sample(tex: &Texture, s: Sampler, u: f32, v: f32) -> f32 {
+        tex.sample_grad_with(&s, u, v, glm::Vec2::zeros(), glm::Vec2::zeros()).x
+    }
+
+    #[test]
+    fn texel_formats() {
+        let rgba = Texture::from_u8(1, 1, TextureFormat::Rgba8, vec![255, 51, 0, 102]).unwrap();
+        assert!(close(rgba.texel(0, 0), [1.0, 0.2, 0.0, 0.4]));
+        let rgb = Texture::from_u8(1, 1, TextureFormat::Rgb8, vec![0, 51, 255]).unwrap();
+        assert!(close(rgb.texel(0, 0), [0.0, 0.2, 1.0, 1.0]), "sin alfa vale 1");
+        let r = Texture::from_u8(1, 1, TextureFormat::R8, vec![51]).unwrap();
+        assert!(close(r.texel(0, 0), [0.2, 0.2, 0.2, 1.0]), "R se replica en gris");
+
+        // Los flotantes no se recortan a [0,1]
+        let r32 = Texture::from_fn(1, 1, TextureFormat::R32F, |_, _| [2.5, 9.0, 9.0, 9.0]);
+        assert!(close(r32.texel(0, 0), [2.5, 2.5, 2.5, 1.0]));
+        let rgba32 = Texture::from_fn(1, 1, TextureFormat::Rgba32F, |_, _| [-1.0, 0.5, 4.0, 2.0]);
+        assert!(close(rgba32.texel(0, 0), [-1.0, 0.5, 4.0, 2.0]));
+        // Y los de 8 bits sí
+        let rgba8 = Texture::from_fn(1, 1, TextureFormat::Rgba8, |_, _| [2.0, -1.0, 0.2, 1.0]);
+        assert!(close(rgba8.texel(0, 0), [1.0, 0.0, 0.2, 1.0]));
+    }
+
+    #[test]
+    fn rejects_bad_data() {
+        assert!(Texture::from_u8(2, 2, TextureFormat::Rgb8, vec![0; 11]).is_err());
+        assert!(Texture::from_u8(0, 2, TextureFormat::R8, Vec::new()).is_err());
+        assert!(Texture::from_u8(1, 1, TextureFormat::R32F, vec![0; 4]).is_err());
+    }
+
+    #[test]
+    fn wrap_modes() {
+        let tex = gray_2x2();
+        let at = |wrap: Wrap, u: f32| sample(&tex, Sampler::new(Filter::Nearest, wrap), u, 0.25);
+        // u = 1.25 cae en la columna 2 y u = -0.25 en la -1
+        assert_eq!([at(Wrap::Repeat, 1.25), at(Wrap::Repeat, -0.25)], [0.0, 0.2]);
+        assert_eq!([at(Wrap::Clamp, 1.25), at(Wrap::Clamp, -0.25)], [0.2, 0.0]);
+        assert_eq!([at(Wrap::Mirror, 1.25), at(Wrap::Mirror, -0.25)], [0.2, 0.0]);
+        assert_eq!(at(Wrap::Mirror, 1.75), 0.0, "la columna 3 refleja la 0");
+
+        // Cada eje con su modo
+        let s = Sampler { wrap_u: Wrap::Clamp, wrap_v: Wrap::Repeat, ..Sampler::new(Filter::Nearest, Wrap::Clamp) };
+        assert_eq!(sample(&tex, s, 1.25, 1.25), 0.2);
+        assert_eq!(sample(&tex, s, -0.25, 1.75), 0.4);
+    }
+
+    #[test]
+    fn nearest_and_bilinear() {
+        let tex = gray_2x2();
+        let nearest = Sampler::new(Filter::Nearest, Wrap::Clamp);
+        let bilinear = Sampler::new(Filter::Bilinear, Wrap::Clamp);
+        assert_eq!(sample(&tex, nearest, 0.5, 0.5), 0.6);
+        assert_eq!(sample(&tex, nearest, 0.49, 0.49), 0.0);
+
+        // Centros de texel exactos, y mezclas entre ellos
+        let b = |u, v| sample(&tex, bilinear, u, v);
+        assert!((b(0.25, 0.25) - 0.0).abs() < 1e-6);
+        assert!((b(0.75, 0.75) - 0.6).abs() < 1e-6);
+        assert!((b(0.5, 0.25) - 0.1).abs() < 1e-6);
+        assert!((b(0.5, 0.5) - 0.3).abs() < 1e-6);
+        // Con Clamp el borde repite el último texel; con Repeat mezcla con el opuesto
+        assert!((b(0.0, 0.25) - 0.0).abs() < 1e-6);
+        let repeat = Sampler::new(Filter::Bilinear, Wrap::Repeat);
+        assert!((sample(&tex, repeat, 0.0, 0.25) - 0.1).abs() < 1e-6);
+    }
+
+    fn sizes(tex: &Texture) -> Vec<(usize, usize)> {
+        tex.levels.iter().map(|l| (l.width, l.height)).collect()
+    }
+
+    #[test]
+    fn mip_chain_sizes() {
+        let tex = |w, h| Texture::from_fn(w, h, TextureFormat::R8, |_, _| [0.0; 4]);
+        assert_eq!(sizes(&tex(8, 4)), [(8, 4), (4, 2), (2, 1), (1, 1)]);
+        assert_eq!(sizes(&tex(5, 3)), [(5, 3), (2, 1), (1, 1)]);
+        assert_eq!(sizes(&tex(1, 7)), [(1, 7), (1, 3), (1, 1)]);
+        assert_eq!(sizes(&tex(1, 1)), [(1, 1)]);
+    }
+
+    #[test]
+    fn mip_levels_average_blocks() {
+        let values = [0.0, 1.0, 2.0, 3.0];
+        let f32 = Texture::from_fn(2, 2, TextureFormat::R32F, |x, y| [values[y * 2 + x]; 4]);
+        assert_eq!(f32.levels[1].texel(f32.format, 0, 0).x, 1.5);
+
+        // En 8 bits el promedio (0.75) se redondea al guardarlo
+        let u8 = Texture::from_u8(2, 2, TextureFormat::R8, vec![0, 255, 255, 255]).unwrap();
+        assert_eq!(u8.levels[1].texel(u8.format, 0, 0).x, 191.0 / 255.0);
+
+        // Con ancho impar la última columna no entra en el bloque
+        let odd = Texture::from_fn(3, 1, TextureFormat::Rgba32F, |x, _| [x as f32 * 3.0, 1.0, 0.0, 1.0]);
+        assert!(close(odd.levels[1].texel(odd.format, 0, 0), [1.5, 1.0, 0.0, 1.0]));
+    }
+
+    // Tablero 4x4 de 0 y 1: el nivel 0 alterna y los siguientes valen 0.5
+    fn checker() -> Texture {
+        Texture::from_fn(4, 4, TextureFormat::R32F, |x, y| [((x + y) % 2) as f32; 4])
+    }
+
+    #[test]
+    fn lod_follows_derivatives() {
+        let tex = checker();
+        let s = Sampler::new(Filter::Trilinear, Wrap::Repeat);
+        let texels = |n: f32| (glm::vec2(n / 4.0, 0.0), glm::vec2(0.0, n / 4.0));
+
+        // Un texel por píxel: nivel 0, igual que el bilineal
+        let (dx, dy) = texels(1.0);
+        assert_eq!(tex.footprint(&s, dx, dy).0, 0.0);
+        assert_eq!(tex.sample_grad_with(&s, 0.125, 0.125, dx, dy).x, 0.0);
+        assert_eq!(tex.sample_grad_with(&s, 0.375, 0.125, dx, dy).x, 1.0);
+        // Menos de un texel se queda en el nivel 0
+        let (dx, dy) = texels(0.25);
+        assert_eq!(tex.sample_grad_with(&s, 0.375, 0.125, dx, dy).x, 1.0);
+
+        // Al crecer la huella sube el nivel y el tablero se promedia
+        let (dx, dy) = texels(2.0);
+        assert_eq!(tex.footprint(&s, dx, dy).0, 1.0);
+        assert_eq!(tex.sample_grad_with(&s, 0.375, 0.125, dx, dy).x, 0.5);
+        let (dx, dy) = texels(4.0);
+        assert_eq!(tex.footprint(&s, dx, dy).0, 2.0);
+        // A medio camino entre los niveles 0 y 1
+        let (dx, dy) = texels(2f32.sqrt());
+        assert!((tex.footprint(&s, dx, dy).0 - 0.5).abs() < 1e-6);
+        assert!((tex.sample_grad_with(&s, 0.375, 0.125, dx, dy).x - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn anisotropy_adds_taps_along_major_axis() {
+        let tex = Texture::from_fn(16, 16, TextureFormat::R8, |_, _| [0.0; 4]);
+        let aniso = Sampler::anisotropic(8);
+        let texel = 1.0 / 16.0;
+        let footprint = |s: &Sampler, lx: f32, ly: f32| {
+            let (lod, samples, axis) = tex.footprint(s, glm::vec2(lx * texel, 0.0), glm::vec2(0.0, ly * texel));
+            (lod, samples, axis.x > 0.0)
+        };
+
+        assert_eq!(footprint(&aniso, 1.0, 1.0), (0.0, 1, true));
+        assert_eq!(footprint(&aniso, 4.0, 4.0), (2.0, 1, true), "isótropa: solo sube el nivel");
+        assert_eq!(footprint(&aniso, 4.0, 1.0), (0.0, 4, true));
+        assert_eq!(footprint(&aniso, 8.0, 1.0), (0.0, 8, true));
+        assert_eq!(footprint(&aniso, 1.0, 8.0), (0.0, 8, false), "el eje largo es y");
+        // Pasado el máximo de muestras, el resto lo absorbe el nivel
+        assert_eq!(footprint(&aniso, 32.0, 1.0), (2.0, 8, true));
+        // Sin anisotropía la misma huella solo sube el nivel
+        let trilinear = Sampler::new(Filter::Trilinear, Wrap::Repeat);
+        assert_eq!(footprint(&trilinear, 4.0, 1.0), (2.0, 1, true));
+    }
+}